@@ -0,0 +1,180 @@
+use makepad_widgets::*;
+use crate::debug_layout::{layout_node, InspectedLayout};
+
+// A grid of `rows` x `columns` cells showing every alignment at once. The cells are instantiated
+// from Rust out of the `cell` template, and each gets the `align` for its position: `x` goes
+// from 0.0 in the first column to 1.0 in the last, `y` from 0.0 in the first row to 1.0 in the
+// last. All cells lay out their children with `cell_flow`, and their `label` shows the flow and
+// alignment that apply to it:
+//
+//     down_matrix = <AlignmentMatrix> {cell_flow: Down}
+//     fine_matrix = <AlignmentMatrix> {rows: 5, columns: 5, cell_flow: Right}
+//
+// The template has two markers besides the label, so the direction of the flow is visible too.
+live_design!{
+    import makepad_widgets::base::*;
+    import makepad_widgets::theme_desktop_dark::*;
+
+    AlignmentMarker = <View> {
+        width: 14,
+        height: 14,
+        show_bg: true,
+    }
+
+    AlignmentCell = <View> {
+        width: Fill,
+        height: 110,
+        show_bg: true,
+        draw_bg: {color: #x444}
+        padding: {left: 4, top: 4, right: 4, bottom: 4},
+        spacing: 4,
+
+        marker_a = <AlignmentMarker> {draw_bg: {color: #xE8A040}}
+        marker_b = <AlignmentMarker> {draw_bg: {color: #x40A0E8}}
+        label = <Label> {
+            draw_text: {
+                text_style: {font_size: 9},
+                color: #xFFFFFFCC
+            }
+            text: ""
+        }
+    }
+
+    AlignmentMatrix = {{AlignmentMatrix}} {
+        width: Fill,
+        height: Fit,
+        flow: Down,
+        spacing: 4,
+        rows: 3,
+        columns: 3,
+        cell_flow: Down,
+        cell: <AlignmentCell> {}
+    }
+}
+
+// The alignment of the cell at `index` of `count` cells along one axis.
+pub fn alignment(index: usize, count: usize) -> f64 {
+    if count <= 1 {
+        0.5
+    } else {
+        index as f64 / (count - 1) as f64
+    }
+}
+
+// The label of a cell, e.g. "Down\nx: 0.5, y: 1".
+pub fn cell_label(flow: Flow, x: f64, y: f64) -> String {
+    format!("{:?}\nx: {}, y: {}", flow, x, y)
+}
+
+#[derive(Live, Widget)]
+pub struct AlignmentMatrix {
+    #[redraw] #[rust] area: Area,
+    #[walk] walk: Walk,
+    #[layout] layout: Layout,
+
+    #[live] rows: usize,
+    #[live] columns: usize,
+    #[live] cell_flow: Flow,
+    #[live] cell: Option<LivePtr>,
+
+    // Row by row.
+    #[rust] cells: Vec<WidgetRef>,
+    // The ids the layout debug overlay shows for the cells, `cell0`, `cell1`, ...
+    #[rust] cell_ids: Vec<LiveId>,
+}
+
+impl LiveHook for AlignmentMatrix {
+    // Rebuilds the cells on every apply, so a live edit of the template or an `apply_over` of the
+    // size or flow shows up right away.
+    fn after_apply(&mut self, cx: &mut Cx, _from: ApplyFrom, _index: usize, _nodes: &[LiveNode]) {
+        self.build_cells(cx);
+    }
+}
+
+impl Widget for AlignmentMatrix {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        for cell in &self.cells {
+            cell.handle_event(cx, event, scope);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        cx.begin_turtle(walk, self.layout);
+        for row in self.cells.chunks(self.columns.max(1)) {
+            cx.begin_turtle(Walk::fill_fit(), Layout {
+                flow: Flow::Right,
+                spacing: self.layout.spacing,
+                ..Layout::default()
+            });
+            for cell in row {
+                let _ = cell.draw_all(cx, scope);
+            }
+            cx.end_turtle();
+        }
+        cx.end_turtle_with_area(&mut self.area);
+        DrawStep::done()
+    }
+}
+
+impl AlignmentMatrix {
+    // Instantiates the cells for the current size and flow.
+    fn build_cells(&mut self, cx: &mut Cx) {
+        self.cells.clear();
+        for index in self.cell_ids.len()..self.rows * self.columns {
+            let name = format!("cell{}", index);
+            self.cell_ids.push(LiveId::from_str_with_lut(&name).unwrap_or_else(|_| LiveId::from_str(&name)));
+        }
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let x = alignment(column, self.columns);
+                let y = alignment(row, self.rows);
+                let cell = WidgetRef::new_from_ptr(cx, self.cell);
+                match self.cell_flow {
+                    Flow::Right => cell.apply_over(cx, live!{flow: Right}),
+                    Flow::Down => cell.apply_over(cx, live!{flow: Down}),
+                    Flow::Overlay => cell.apply_over(cx, live!{flow: Overlay}),
+                    Flow::RightWrap => cell.apply_over(cx, live!{flow: RightWrap}),
+                }
+                cell.apply_over(cx, live!{
+                    align: {x: (x), y: (y)}
+                });
+                cell.label(id!(label)).set_text(&cell_label(self.cell_flow, x, y));
+                self.cells.push(cell);
+            }
+        }
+        self.area.redraw(cx);
+    }
+}
+
+impl AlignmentMatrixRef {
+    // Regenerates the cells for a new size.
+    pub fn set_size(&self, cx: &mut Cx, rows: usize, columns: usize) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.rows = rows;
+            inner.columns = columns;
+            inner.build_cells(cx);
+        }
+    }
+
+    // The labels of the cells, row by row.
+    pub fn cell_labels(&self) -> Vec<String> {
+        self.borrow()
+            .map(|inner| inner.cells.iter().map(|cell| cell.label(id!(label)).text()).collect())
+            .unwrap_or_default()
+    }
+}
+
+// Reports the padding and the cells of an `AlignmentMatrix` to the layout debug overlay, see
+// `debug_layout.rs`.
+pub fn inspect_layout(cx: &mut Cx, widget: &WidgetRef) -> Option<InspectedLayout> {
+    let inner = widget.borrow::<AlignmentMatrix>()?;
+    let children = match inner.cell {
+        Some(template) => inner.cell_ids
+            .iter()
+            .zip(&inner.cells)
+            .map(|(id, cell)| layout_node(cx, *id, cell.clone(), template))
+            .collect(),
+        None => Vec::new(),
+    };
+    Some(InspectedLayout {padding: Some(inner.layout.padding), children})
+}
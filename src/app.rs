@@ -1,4 +1,21 @@
 use makepad_widgets::*;
+use crate::alignment_matrix::AlignmentMatrix;
+use crate::counter::{round_to, CounterWidgetWidgetRefExt};
+use crate::debug_layout::{layout_tree, register_layout_inspector, LayoutNode, LayoutOverlayWidgetRefExt};
+use crate::history::{Change, History};
+use crate::launch::{AppMode, LaunchOptions};
+use crate::persist::{load_store, save_store};
+use crate::reactive::Reactor;
+use crate::repeat_button::RepeatButtonAction;
+use crate::responsive::{ResponsiveView, ResponsiveViewWidgetRefExt};
+use crate::shortcuts::{CounterCommand, CounterShortcuts};
+use crate::text_area::text_stats;
+use crate::transform::{TextTransform, TransformRegistry};
+use crate::drop_down_enum;
+use crate::dropdown_items::{DropDownItem, DropDownItemsExt};
+use crate::typed_dropdown::{read_enum, DropDownEnum, DropDownEnumExt};
+use crate::validation::ValidatedInputWidgetRefExt;
+use std::path::PathBuf;
 
 // The live_design macro generates a function that registers a DSL code block with the global
 // context object (`Cx`).
@@ -13,6 +30,17 @@ live_design!{
     import makepad_widgets::theme_desktop_dark::*;
     import makepad_draw::shader::std::*;
 
+    import crate::counter::*;
+    import crate::combo_box::*;
+    import crate::multi_select::*;
+    import crate::validation::*;
+    import crate::password_input::*;
+    import crate::text_area::*;
+    import crate::layout_playground::*;
+    import crate::alignment_matrix::*;
+    import crate::responsive::*;
+    import crate::debug_layout::*;
+
     SCREEN_WIDTH = 1200;
     FONT_SIZE_H2 = 9.5
     
@@ -134,6 +162,54 @@ live_design!{
         }
     }
 
+    // A `ComboBox` that looks like `FishDropDown`, with a popup like its menu.
+    FishComboBox = <ComboBox> {
+        width: Fit,
+        padding: {
+            top: (SSPACING_2),
+            right: (SSPACING_4),
+            bottom: (SSPACING_2),
+            left: (SSPACING_2)
+        },
+
+        draw_text: {
+            text_style: <H2_TEXT_NORMAL> {},
+            color: #xFFF8
+        }
+
+        draw_bg: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.rect(0, 0, self.rect_size.x, self.rect_size.y)
+                sdf.fill((COLOR_UP_0))
+                // triangle
+                let c = vec2(self.rect_size.x - 10.0, self.rect_size.y * 0.5)
+                let sz = 2.5;
+
+                sdf.move_to(c.x - sz, c.y - sz);
+                sdf.line_to(c.x + sz, c.y - sz);
+                sdf.line_to(c.x, c.y + sz * 0.75);
+                sdf.close_path();
+
+                sdf.fill(mix(#FFFA, #FFFF, max(self.hover, self.selected)));
+
+                return sdf.result
+            }
+        }
+
+        popup: {
+            item_padding: {
+                left: (SSPACING_4),
+                top: (SSPACING_2),
+                bottom: (SSPACING_2),
+                right: (SSPACING_4)
+            },
+            draw_item_text: {
+                text_style: <H2_TEXT_NORMAL> {},
+            }
+        }
+    }
+
     InstrumentDropdown = <ElementBox> {
         align: {y: 0.5},
         padding: <SPACING_0> {}, 
@@ -150,6 +226,31 @@ live_design!{
         }
     }
 
+    // Like `InstrumentDropdown`, but any number of items can be selected.
+    InstrumentMultiSelect = <ElementBox> {
+        align: {y: 0.5},
+        padding: <SPACING_0> {},
+        flow: Right,
+        label = <Label> {
+            width: Fit,
+            draw_text: {
+                color: (COLOR_UP_5)
+                text_style: <H2_TEXT_BOLD>{},
+            }
+        }
+        dropdown = <MultiSelectDropDown> {
+            margin: {left: (SSPACING_1), right: (SSPACING_1)}
+            draw_text: {
+                text_style: <H2_TEXT_NORMAL> {},
+            }
+            popup: {
+                draw_item_text: {
+                    text_style: <H2_TEXT_NORMAL> {},
+                }
+            }
+        }
+    }
+
     WidgetView = <View>{
         show_bg: true
         // The `walk` property determines how the frame widget itself is laid out. In this
@@ -193,87 +294,221 @@ live_design!{
         // they simply iterate over their instance properties, and use them to instantiate their
         // child widgets.
         
-        // A button to increment the counter.
+        // A counter with a button to increment it, a label showing its value and a button to
+        // decrement it. The `CounterWidget` (see `counter.rs`) owns the value and updates the
+        // label by itself, so any number of counters can be dropped into a view.
         //
-        // The `<Button>` syntax is used to inherit a DSL object from another DSL object. This
-        // tells the Makepad runtime our DSL object has the same properties as the DSL object
-        // named `Button`, except for the properties defined here below, which override any
-
-        ButtonView = <View> {
+        // The `<CounterWidget>` syntax is used to inherit a DSL object from another DSL object.
+        // This tells the Makepad runtime our DSL object has the same properties as the DSL object
+        // named `CounterWidget`, except for the properties defined here below, which override any
+        // inherited ones. The `button1 = { ... }` syntax does the same for an existing child.
+        //
+        // The undo and redo buttons are placed beside the counter, or below it in windows less
+        // than 800 pixels wide, see `responsive.rs`.
+        ButtonView = <ResponsiveView> {
             width: Fit,
             height: Fit,
-            flow: Down,
-            spacing: 20,
-            align: {
-                x: 0.5,
-                y: 0.5
+            breakpoint: 800,
+            wide: {flow: Right, spacing: 20, align: {x: 0.5, y: 0.5}},
+            narrow: {flow: Down, spacing: 10, align: {x: 0.5, y: 0.5}},
+
+            counter = <CounterWidget> {
+                min: 0,
+                step: 1,
+                // `Wrap` continues from the other bound, `Reject` ignores the step and shakes.
+                overflow: Clamp,
+                // Decimals shown, e.g. with `step: 0.25, precision: 2`.
+                precision: 0,
+                format: "Label: {}"
+
+                // Holding the button keeps incrementing, see `repeat_button.rs`.
+                button1 = {
+                    initial_delay: 0.4,
+                    interval: 0.12,
+                    acceleration: 0.85,
+                    button = {
+                        // icon_walk:{margin:{left:10}, width:16,height:Fit}
+                        draw_text: {
+                            text_style:<REGULAR_TEXT>{},
+                            color: #f
+                        },
+                        text: "Button +"
+                    }
+                }
+
+                label1 = {
+                    align: {
+                        x: 0.3,
+                        // y: 1
+                    }
+                    draw_text: {
+                        text_style:<REGULAR_TEXT>{},
+                        color: #f
+                    },
+                    text: "Label: 0"
+                }
+
+                button2 = {
+                    button = {
+                        // icon_walk:{margin:{left:10}, width:16,height:Fit}
+                        draw_text: {
+                            text_style:<REGULAR_TEXT>{},
+                            color: #f
+                        },
+                        text: "Button -"
+                    }
+                }
             }
-            button1 = <Button> {
-                // icon_walk:{margin:{left:10}, width:16,height:Fit}
-                draw_text: {
-                    text_style:<REGULAR_TEXT>{},
-                    color: #f
-                },
-                text: "Button +"
+
+            // Undo and redo the edits made in `WidgetView`, like Ctrl+Z and Ctrl+Shift+Z do. Both
+            // buttons are optional, the app only reacts to them if they exist.
+            HistoryBar = <View> {
+                width: Fit,
+                height: Fit,
+                flow: Right,
+                spacing: 10,
+
+                undo_button = <Button> {
+                    draw_text: {
+                        text_style:<REGULAR_TEXT>{},
+                        color: #f
+                    },
+                    text: "Undo"
+                }
+                redo_button = <Button> {
+                    draw_text: {
+                        text_style:<REGULAR_TEXT>{},
+                        color: #f
+                    },
+                    text: "Redo"
+                }
             }
+        }
+
+        // On phones, the controls are stacked instead of placed side by side.
+        InputView = <ResponsiveView> {
+            width: Fill,
+            height: Fit,
+            breakpoint: 800,
+            wide: {flow: Right, spacing: 10, align: {x: 0.5, y: 0.0}},
+            narrow: {flow: Down, spacing: 4, align: {x: 0.0, y: 0.0}},
 
-            label1 = <Label> {
-                width: 100,
+            label_input = <Label> {
+                height:30,
                 align: {
-                    x: 0.3,
-                    // y: 1
+                    // x: 1.5,
+                    y: 1
                 }
                 draw_text: {
-                    text_style:<REGULAR_TEXT>{},
+                    text_style: { font_size: 16 }
                     color: #f
                 },
-                text: "Label: 0"
+                text: "Text:"
             }
 
-            button2 = <Button> {
-                // icon_walk:{margin:{left:10}, width:16,height:Fit}
+            // A text input with validators, see `validation.rs`. Invalid text turns the input red
+            // and shows the error underneath.
+            input_sample = <ValidatedInput> {
+                max_length: 40,
+                input = {
+                    // instance border_width: 2.0,
+                    // width:500, height:30,
+                    // Shown while the input is empty. It is only drawn, `text()` stays empty.
+                    empty_message: "Enter Text Here"
+                    draw_text: {
+                        text_style: { font_size: 16 }
+                        color: #aaaaaa
+                        // `is_empty` is 1.0 while the placeholder is drawn, so it is dimmed.
+                        fn get_color(self) -> vec4 {
+                            return mix(self.color, #x666666, self.is_empty)
+                        }
+                    }
+                    text: ""
+                }
+            }
+
+            // When checked, the input edits the counter above: typing a number sets it, and
+            // stepping it updates the text.
+            number_field = <CheckBox> {
                 draw_text: {
                     text_style:<REGULAR_TEXT>{},
                     color: #f
                 },
-                text: "Button -"
+                text: "Number field"
+            }
+
+            // A multi-line text area after the single-line input, with its line, word and
+            // character counts underneath. It fills the rest of the row, or the whole width on
+            // phones, so it comes last: a `Fill` child takes what is left of the row when it is
+            // drawn.
+            notes_view = <View> {
+                width: Fill,
+                height: Fit,
+                flow: Down,
+                spacing: 4,
+
+                notes = <TextArea> {
+                    width: Fill,
+                    height: 120,
+                    input = {
+                        empty_message: "Notes"
+                        draw_text: {
+                            text_style: { font_size: 12 }
+                            color: #aaaaaa
+                            fn get_color(self) -> vec4 {
+                                return mix(self.color, #x666666, self.is_empty)
+                            }
+                        }
+                        text: ""
+                    }
+                }
+
+                notes_stats = <Label> {
+                    draw_text: {
+                        text_style: { font_size: 10 }
+                        color: #xFFFFFF88
+                    },
+                    text: ""
+                }
             }
         }
 
-        InputView = <View> {
+        // A second input row with a password field, as on a login screen. The text is drawn as
+        // bullets until the eye button is clicked, see `password_input.rs`.
+        PasswordView = <View> {
             width: Fit,
             height: Fit,
             flow: Right,
             spacing: 10,
             align: {
                 x: 0.5,
-                y: 0.0
+                y: 0.5
             },
 
-            label_input = <Label> {
+            label_password = <Label> {
                 height:30,
                 align: {
-                    // x: 1.5,
                     y: 1
                 }
                 draw_text: {
                     text_style: { font_size: 16 }
                     color: #f
                 },
-                text: "Text:"
+                text: "Password:"
             }
 
-            input_sample = <TextInput> {
-                // instance border_width: 2.0,
-                // width:500, height:30,
-                draw_bg: {
-                    color: #333
-                }
-                draw_text: {
-                    text_style: { font_size: 16 }
-                    color: #aaaaaa
+            password_input = <PasswordInput> {
+                input = {
+                    empty_message: "Password"
+                    draw_text: {
+                        text_style: { font_size: 16 }
+                        color: #aaaaaa
+                        fn get_color(self) -> vec4 {
+                            return mix(self.color, #x666666, self.is_empty)
+                        }
+                    }
+                    text: ""
                 }
-                text: "Enter Text Here"
             }
         }
 
@@ -296,10 +531,29 @@ live_design!{
                     // text_style:<REGULAR_TEXT>{},
                     color: #f
                 },
-                text: "Dropdown:"
+                text: "Show input as:"
             }
 
+            // How `input_sample` is shown in `label_input`. The items are the transforms
+            // registered in `App::transforms`, see `transform.rs`.
             my_dropdown = <InstrumentDropdown> {
+                width: Fit,
+                height: 30,
+                margin: {
+                    top: (SPACING_CONTROLS),
+                    right: (SPACING_CONTROLS),
+                    bottom: (SPACING_CONTROLS),
+                    left: 0.0
+                },
+                flow: Down,
+                dropdown = {
+                    values: []
+                    labels: []
+                }
+            }
+
+            // Bound to the `Selection` enum, see `typed_dropdown.rs`.
+            selection_dropdown = <InstrumentDropdown> {
                 width: Fit,
                 height: 30,
                 margin: {
@@ -315,25 +569,160 @@ live_design!{
                 }
             }
         }
+
+        // A dropdown whose items are set from Rust at runtime, see `App::set_items`.
+        ItemsView = <View> {
+            width: Fit,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {
+                x: 0.5,
+                y: 0.5
+            },
+            label_items = <Label> {
+                draw_text: {
+                    color: #f
+                },
+                text: "Items:"
+            }
+
+            items_dropdown = <InstrumentDropdown> {
+                width: Fit,
+                height: 30,
+                flow: Down,
+                dropdown = {
+                    values: []
+                    labels: []
+                }
+            }
+
+            add_item_button = <Button> {
+                text: "Add item"
+            }
+        }
+
+        // A dropdown where several items can be selected, see `multi_select.rs`.
+        MultiSelectView = <View> {
+            width: Fit,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {
+                x: 0.5,
+                y: 0.5
+            },
+            label_multi_select = <Label> {
+                draw_text: {
+                    color: #f
+                },
+                text: "Multi-select:"
+            }
+
+            my_multi_select = <InstrumentMultiSelect> {
+                width: Fit,
+                height: 30,
+                flow: Down,
+                dropdown = {
+                    values: [sel1, sel2, sel3, sel4]
+                    labels: ["Selection 1", "Selection 2", "Selection 3","Selection 4"]
+                }
+            }
+        }
+
+        // A searchable dropdown for long lists, see `combo_box.rs`.
+        ComboView = <View> {
+            width: Fit,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {
+                x: 0.5,
+                y: 0.5
+            },
+            label_combo = <Label> {
+                draw_text: {
+                    color: #f
+                },
+                text: "Search:"
+            }
+
+            instrument_combo = <FishComboBox> {
+                values: [
+                    accordion, bagpipes, banjo, bass, bassoon, cello, clarinet, drums, flute,
+                    guitar, harp, harpsichord, oboe, organ, piano, saxophone, synthesizer,
+                    trombone, trumpet, tuba, ukulele, viola, violin, xylophone
+                ]
+                labels: [
+                    "Accordion", "Bagpipes", "Banjo", "Bass", "Bassoon", "Cello", "Clarinet",
+                    "Drums", "Flute", "Guitar", "Harp", "Harpsichord", "Oboe", "Organ", "Piano",
+                    "Saxophone", "Synthesizer", "Trombone", "Trumpet", "Tuba", "Ukulele", "Viola",
+                    "Violin", "Xylophone"
+                ]
+            }
+        }
+    }
+
+    // Every combination of alignment and `flow: Down`/`Right`, in grids generated from Rust by
+    // `AlignmentMatrix` (see `alignment_matrix.rs`).
+    AlignmentView = <View> {
+        width: Fill,
+        height: Fill,
+        flow: Right,
+        spacing: 20,
+        padding: {left: 20, top: 20, right: 20, bottom: 20},
+        show_bg: true,
+        draw_bg: {color: #x555}
+
+        DownColumn = <View> {
+            width: Fill,
+            height: Fill,
+            flow: Down,
+            spacing: 10,
+            <Label> {draw_text: {color: #f}, text: "flow: Down"}
+            down_matrix = <AlignmentMatrix> {cell_flow: Down}
+        }
+
+        RightColumn = <View> {
+            width: Fill,
+            height: Fill,
+            flow: Down,
+            spacing: 10,
+            <Label> {draw_text: {color: #f}, text: "flow: Right"}
+            right_matrix = <AlignmentMatrix> {cell_flow: Right}
+        }
     }
 
+    // A tab in the navigation bar of the shell window, and in the one of `LayoutView`. The
+    // `selected` instance variable is set from Rust when the tab's page is the active one.
+    NavTab = <Button> {
+        width: Fill,
+        height: 40,
+        draw_text: {
+            text_style: <H2_TEXT_BOLD> {},
+            fn get_color(self) -> vec4 {
+                return mix(#xFFF8, #xFFFF, self.hover)
+            }
+        }
+        draw_bg: {
+            instance selected: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.rect(0., 0., self.rect_size.x, self.rect_size.y);
+                sdf.fill(mix(mix(#x2A, #x3A, self.hover), #x44, self.selected));
+                // underline the active tab
+                sdf.rect(0., self.rect_size.y - 3.0, self.rect_size.x, 3.0);
+                sdf.fill(mix((COLOR_UP_0), (COLOR_UP_5), self.selected));
+                return sdf.result
+            }
+        }
+    }
+
+    // The row/column layouts, a playground to try layout properties on and the alignment matrix,
+    // one at a time, picked with the tabs at the top, see `App::set_layout_section`.
     LayoutView = <View>{
         show_bg: true
-        // The layout properties determines how child widgets are laid out within a frame. In
-        // this case, child widgets flow downward, with 20 pixels of spacing in between them,
-        // and centered horizontally with respect to the entire frame.
-        //
-        // Because the child widgets flow downward, vertical alignment works somewhat
-        // differently. In this case, children are centered vertically with respect to the
-        // remainder of the frame after the previous children have been drawn.
         flow: Down,
-        // spacing: 20,
-        align: {
-            // x: 0.5,
-            // y: 0.5
-        },
-        // These property determines how the frame widget itself is laid out. In this
-        // case, the frame widget takes up the entire window.
         width: Fill,
         height: Fill,
         draw_bg: {
@@ -349,71 +738,141 @@ live_design!{
             }
         }
 
-        RowView1 = <View> {
-            flow: Down,
-            align: {
-                x: 0.5,
-                y: 0.0
-            },
-            buttonc1 = <Button> {
-                width: 120,
-                text: "Button Top"
-            }
+        layout_tabs = <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+
+            rows_tab = <NavTab> {text: "Rows", draw_bg: {selected: 1.0}}
+            playground_tab = <NavTab> {text: "Playground"}
+            alignment_tab = <NavTab> {text: "Alignment"}
         }
 
-        RowView2 = <View> {
-            flow: Right,
+        rows_section = <View> {
+            // The layout properties determines how child widgets are laid out within a frame. In
+            // this case, child widgets flow downward, with 20 pixels of spacing in between them,
+            // and centered horizontally with respect to the entire frame.
+            //
+            // Because the child widgets flow downward, vertical alignment works somewhat
+            // differently. In this case, children are centered vertically with respect to the
+            // remainder of the frame after the previous children have been drawn.
+            flow: Down,
+            // spacing: 20,
             align: {
-                x: 0.0,
-                y: 0.0
+                // x: 0.5,
+                // y: 0.5
             },
+            // These property determines how the frame widget itself is laid out. In this
+            // case, the frame widget takes up the rest of the window.
+            width: Fill,
+            height: Fill,
 
-            ColumnView1 = <View> {
+            RowView1 = <View> {
                 flow: Down,
                 align: {
-                    x: 0.0,
-                    y: 0.5
+                    x: 0.5,
+                    y: 0.0
                 },
-                buttonc21 = <Button> {
+                buttonc1 = <Button> {
                     width: 120,
-                    text: "Button Left"
+                    text: "Button Top"
                 }
             }
 
-            ColumnView2 = <View> {
-                flow: Down,
+            RowView2 = <View> {
+                flow: Right,
                 align: {
-                    x: 0.5,
-                    y: 0.5
+                    x: 0.0,
+                    y: 0.0
                 },
-                buttonc22 = <Button> {
-                    width: 120,
-                    text: "Button Center"
+
+                ColumnView1 = <View> {
+                    flow: Down,
+                    align: {
+                        x: 0.0,
+                        y: 0.5
+                    },
+                    buttonc21 = <Button> {
+                        width: 120,
+                        text: "Button Left"
+                    }
+                }
+
+                ColumnView2 = <View> {
+                    flow: Down,
+                    align: {
+                        x: 0.5,
+                        y: 0.5
+                    },
+                    buttonc22 = <Button> {
+                        width: 120,
+                        text: "Button Center"
+                    }
                 }
-            }
 
-            ColumnView3 = <View> {
+                ColumnView3 = <View> {
+                    flow: Down,
+                    align: {
+                        x: 1.0,
+                        y: 0.5
+                    },
+                    buttonc23 = <Button> {
+                        width: 120,
+                        text: "Button Right"
+                    }
+                }
+            }
+            RowView3 = <View> {
                 flow: Down,
                 align: {
-                    x: 1.0,
-                    y: 0.5
+                    x: 0.5,
+                    y: 1.0
                 },
-                buttonc23 = <Button> {
+                buttonc3 = <Button> {
                     width: 120,
-                    text: "Button Right"
+                    text: "Button Bottom"
                 }
             }
+    
         }
-        RowView3 = <View> {
-            flow: Down,
-            align: {
-                x: 0.5,
-                y: 1.0
-            },
-            buttonc3 = <Button> {
-                width: 120,
-                text: "Button Bottom"
-            }
+
+        // Layout properties changed live from side controls, see `layout_playground.rs`.
+        playground_section = <View> {
+            visible: false,
+            playground = <LayoutPlayground> {}
+        }
+
+        alignment_section = <AlignmentView> {visible: false}
+    }
+
+    SlidesDeck = <SlidesView> {
+        <SlideChapter> {
+            title = {text: "MAKEPAD\nWIDGETS"},
+            <SlideBody> {text: "\n"}
+        }
+        <Slide> {
+            title = {text: ""},
+            <SlideBody> {text: "Widgets"}
+        }
+        <Slide> {title = {text: ""}, 
+            // <RoundedView>{
+            //     draw_bg: { color: #x2A }
+            //     width: (SCREEN_WIDTH)
+            //     padding: 0.0
+                <WidgetView> {}
+            // }
+        }
+        <Slide> {
+            title = {text: ""},
+            <SlideBody> {text: "Layout"}
+        }
+        <Slide> {title = {text: ""}, 
+            // <RoundedView>{
+            //     draw_bg: { color: #x2A }
+            //     width: (SCREEN_WIDTH),
+            //     padding: 0.0,
+                <LayoutView> {}
+            // }
         }
     }
 
@@ -422,58 +881,84 @@ live_design!{
     // instance of `App` is initialized, the Makepad runtime will obtain its initial values from
     // this DSL object.
     App = {{App}} {
-        // The `ui` field on the struct `App` defines a frame widget. Frames are used as containers
-        // for other widgets. Since the `ui` property on the DSL object `App` corresponds with the
-        // `ui` field on the Rust struct `App`, the latter will be initialized from the DSL object
-        // here below.
- 
-        // following is one "version" of this sample app. make it ui:<Window> to activate, ui=<Window> to deactivate
-        ui:<Window>{
+        // Each of the following window trees is one "version" of this sample app. The
+        // `shell_ui`, `widgets_ui`, `layout_ui` and `slides_ui` fields on the struct `App` are only pointers
+        // into this DSL object: at startup, `App` instantiates the one selected by the launch mode
+        // (see `launch.rs`) as its `ui` frame widget, and the others are never created.
+
+        // The keys driving the `counter` while no text input has the keyboard focus, see
+        // `shortcuts.rs` for the key names.
+        counter_shortcuts: {
+            increment: ["Equals", "NumpadAdd", "ArrowUp"],
+            decrement: ["Minus", "NumpadSubtract", "ArrowDown"],
+            page_up: ["PageUp"],
+            page_down: ["PageDown"],
+            reset: ["Home"],
+        }
+
+        // A navigation shell with a tab per demo, switched at runtime. Selected with
+        // `--mode shell` (the default).
+        shell_ui: <Window> {
+            body = <View> {
+                width: Fill,
+                height: Fill,
+                flow: Down,
+
+                nav = <View> {
+                    width: Fill,
+                    height: Fit,
+                    flow: Right,
+                    show_bg: true,
+                    draw_bg: {color: #x2A}
+
+                    widgets_tab = <NavTab> {text: "Widgets", draw_bg: {selected: 1.0}}
+                    layout_tab = <NavTab> {text: "Layout"}
+                    slides_tab = <NavTab> {text: "Slides"}
+                }
+
+                // Only the page of the active tab is visible, see `App::set_page`.
+                pages = <View> {
+                    width: Fill,
+                    height: Fill,
+                    widgets_page = <WidgetView> {}
+                    layout_page = <LayoutView> {visible: false}
+                    // The same deck as `slides_ui`. Its demos repeat the ids of the other pages,
+                    // so `App` looks widgets up in the page shown, see `App::demo_ui`.
+                    slides_page = <View> {
+                        visible: false,
+                        <SlidesDeck> {}
+                    }
+                }
+            }
+
+            // Widget bounds and ids, toggled with F12, see `debug_layout.rs`.
+            debug_overlay = <LayoutOverlay> {}
+        }
+
+        // Basic widgets. Selected with `--mode widgets`.
+        widgets_ui: <Window>{
             body = <WidgetView> {}
+
+            debug_overlay = <LayoutOverlay> {}
         }
 
-        // following is another "version" of this sample app. make it ui:<Window> to activate, ui=<Window> to deactivate
-        ui=<Window> {
+        // Row/column layouts. Selected with `--mode layout`.
+        layout_ui: <Window> {
             body = <LayoutView> {}
+
+            debug_overlay = <LayoutOverlay> {}
         }
 
-        // following is a third "version" of this sample app, combining both above in a slide view. make it ui:<Window> to activate, ui=<Window> to deactivate
-        ui=<Window> {
+        // Both of the above combined in a slide view. Selected with `--mode slides`.
+        slides_ui: <Window> {
             window: {inner_size: vec2(1280, 1080)}, // optimized for desktop window size, not mobile as mobile does not support slide swiping, etc.
             pass: {clear_color: #2A}
             block_signal_event: true; 
             body = <View> {
-                <SlidesView> {
-                    <SlideChapter> {
-                        title = {text: "MAKEPAD\nWIDGETS"},
-                        <SlideBody> {text: "\n"}
-                    }
-                    <Slide> {
-                        title = {text: ""},
-                        <SlideBody> {text: "Widgets"}
-                    }
-                    <Slide> {title = {text: ""}, 
-                        // <RoundedView>{
-                        //     draw_bg: { color: #x2A }
-                        //     width: (SCREEN_WIDTH)
-                        //     padding: 0.0
-                            <WidgetView> {}
-                        // }
-                    }
-                    <Slide> {
-                        title = {text: ""},
-                        <SlideBody> {text: "Layout"}
-                    }
-                    <Slide> {title = {text: ""}, 
-                        // <RoundedView>{
-                        //     draw_bg: { color: #x2A }
-                        //     width: (SCREEN_WIDTH),
-                        //     padding: 0.0,
-                            <LayoutView> {}
-                        // }
-                    }
-                }
+                <SlidesDeck> {}
             }
+
+            debug_overlay = <LayoutOverlay> {}
         }
     }
 }
@@ -489,81 +974,728 @@ app_main!(App);
 
 // The main application struct.
 //
-// The #[derive(Live, LiveHook)] attribute implements a bunch of traits for this struct that enable
-// it to interact with the Makepad runtime. Among other things, this enables the Makepad runtime to
-// initialize the struct from a DSL object.
-#[derive(Live, LiveHook)]
+// The #[derive(Live)] attribute implements a bunch of traits for this struct that enable it to
+// interact with the Makepad runtime. Among other things, this enables the Makepad runtime to
+// initialize the struct from a DSL object. `LiveHook` is implemented by hand below, to create the
+// window for the selected mode.
+#[derive(Live)]
 // This function is used to register any DSL code that you depend on.
 // called automatically by the code we generated with the call to the macro `main_app` above.
 pub struct App {
+    // Pointers to the window trees for each mode. Only the selected one is ever instantiated.
+    #[live] shell_ui: Option<LivePtr>,
+    #[live] widgets_ui: Option<LivePtr>,
+    #[live] layout_ui: Option<LivePtr>,
+    #[live] slides_ui: Option<LivePtr>,
+    #[live] counter_shortcuts: CounterShortcuts,
+
     // A chromeless window for our application. Used to contain our frame widget.
     // A frame widget. Used to contain our button and label.
-    #[live] ui: WidgetRef,
+    #[rust] ui: WidgetRef,
+    // The mode the app was launched in.
+    #[rust] mode: AppMode,
+    // The page shown by the navigation shell. Only used in `AppMode::Shell`.
+    #[rust] page: ShellPage,
+    // The section shown by `LayoutView`.
+    #[rust] layout_section: LayoutSection,
+    // The values bound to widgets in `data_bind`. Kept across frames, saved to `state_file` on
+    // exit and restored from it on startup.
+    #[rust(DataBindingStore::new())] store: DataBindingStore,
+    #[rust] state_file: Option<PathBuf>,
+    // The updates run in response to actions, see `subscribe_updates`.
+    #[rust] reactor: Reactor<App>,
+    // The edits of the fields in `HISTORY_FIELDS`, undone with Ctrl+Z and redone with Ctrl+Shift+Z.
+    #[rust] history: History,
+    // The area with the keyboard focus, if any, as reported by `Event::KeyFocus`. Keys go to that
+    // widget, e.g. a text input or the filter of a combo box, rather than to `counter_shortcuts`,
+    // and Ctrl+Z undoes its own edits rather than those in `history`.
+    #[rust] key_focus: Area,
+    // Whether `input_sample` is bound to the counter, toggled with the `number_field` checkbox.
+    #[rust] number_field: bool,
+    // The transforms `my_dropdown` picks from, the one selected is applied to `label_input`.
+    #[rust(TransformRegistry::with_builtins())] transforms: TransformRegistry,
+    // The DSL of the window `ui` was created from.
+    #[rust] window: Option<LivePtr>,
+    // The width of the window, once resized, which picks the layout of `RESPONSIVE_VIEWS`.
+    #[rust] window_width: Option<f64>,
+    // Whether the layout debug overlay is shown, toggled with F12.
+    #[rust] debug_layout: bool,
     // #[live] label_example: LabelRef,
     // #[live] input_sample: TextInput,
-
-    // The value for our counter.
-    //
-    // The #[rust] attribute here is used to indicate that this field should *not* be initialized
-    // from a DSL object, even when a corresponding property exists.
-    #[rust] counter: usize,
     // #[rust] sample: String,
 }
 
+// The pages of the navigation shell, each with a tab in the `nav` bar.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ShellPage {
+    #[default]
+    Widgets,
+    Layout,
+    Slides,
+}
+
+impl ShellPage {
+    pub const ALL: [ShellPage; 3] = [ShellPage::Widgets, ShellPage::Layout, ShellPage::Slides];
+
+    fn tab_id(self) -> &'static [LiveId] {
+        match self {
+            ShellPage::Widgets => id!(widgets_tab),
+            ShellPage::Layout => id!(layout_tab),
+            ShellPage::Slides => id!(slides_tab),
+        }
+    }
+
+    fn page_id(self) -> &'static [LiveId] {
+        match self {
+            ShellPage::Widgets => id!(widgets_page),
+            ShellPage::Layout => id!(layout_page),
+            ShellPage::Slides => id!(slides_page),
+        }
+    }
+}
+
+// The sections of `LayoutView`, each with a tab in its `layout_tabs` bar.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LayoutSection {
+    #[default]
+    Rows,
+    Playground,
+    Alignment,
+}
+
+impl LayoutSection {
+    pub const ALL: [LayoutSection; 3] = [
+        LayoutSection::Rows,
+        LayoutSection::Playground,
+        LayoutSection::Alignment,
+    ];
+
+    fn tab_id(self) -> &'static [LiveId] {
+        match self {
+            LayoutSection::Rows => id!(rows_tab),
+            LayoutSection::Playground => id!(playground_tab),
+            LayoutSection::Alignment => id!(alignment_tab),
+        }
+    }
+
+    fn section_id(self) -> &'static [LiveId] {
+        match self {
+            LayoutSection::Rows => id!(rows_section),
+            LayoutSection::Playground => id!(playground_section),
+            LayoutSection::Alignment => id!(alignment_section),
+        }
+    }
+}
+
+drop_down_enum!{
+    // The items of `selection_dropdown`, matching its `values` in the DSL.
+    pub enum Selection {
+        Selection1 = sel1,
+        Selection2 = sel2,
+        Selection3 = sel3,
+        Selection4 = sel4,
+    }
+}
+
+impl LiveHook for App {
+    fn after_new_from_doc(&mut self, cx: &mut Cx) {
+        let options = LaunchOptions::current();
+        self.state_file = options.state_file.resolve(cx);
+        self.reactor.set_debug(options.debug_redraws);
+        self.debug_layout = options.debug_layout;
+        self.subscribe_updates();
+        self.set_mode(cx, options.mode);
+    }
+
+    // On live reload, updates the running window from the edited DSL of its mode, keeping the
+    // state of its widgets.
+    fn after_apply(&mut self, cx: &mut Cx, from: ApplyFrom, index: usize, nodes: &[LiveNode]) {
+        if let ApplyFrom::UpdateFromDoc {..} = from {
+            if let Some(window_index) = nodes.child_by_name(index, window_field(self.mode).as_field()) {
+                self.ui.apply(cx, from, window_index, nodes);
+            }
+        }
+    }
+}
+
+// The field of `App` with the window tree for `mode`.
+fn window_field(mode: AppMode) -> LiveId {
+    match mode {
+        AppMode::Shell => live_id!(shell_ui),
+        AppMode::Widgets => live_id!(widgets_ui),
+        AppMode::Layout => live_id!(layout_ui),
+        AppMode::Slides => live_id!(slides_ui),
+    }
+}
+
 impl LiveRegister for App {
     fn live_register(cx: &mut Cx) {
         crate::makepad_widgets::live_design(cx);
+        crate::repeat_button::live_design(cx);
+        crate::counter::live_design(cx);
+        crate::popup_list::live_design(cx);
+        crate::combo_box::live_design(cx);
+        crate::multi_select::live_design(cx);
+        crate::validation::live_design(cx);
+        crate::password_input::live_design(cx);
+        crate::text_area::live_design(cx);
+        crate::layout_playground::live_design(cx);
+        crate::alignment_matrix::live_design(cx);
+        crate::responsive::live_design(cx);
+        crate::debug_layout::live_design(cx);
+        register_layout_inspector::<AlignmentMatrix>(crate::alignment_matrix::inspect_layout);
+        register_layout_inspector::<ResponsiveView>(crate::responsive::inspect_layout);
     }
 }
 
 impl App {
-    pub fn data_bind(&mut self, mut db: DataBindingMap) {
-        db.bind(id!(my_dropdown), ids!(my_dropdown.dropdown));
+    pub fn mode(&self) -> AppMode {
+        self.mode
+    }
+
+    // Replaces the `ui` frame widget with a new instance of the window tree for the given mode.
+    pub fn set_mode(&mut self, cx: &mut Cx, mode: AppMode) {
+        self.mode = mode;
+        self.page = ShellPage::default();
+        self.layout_section = LayoutSection::default();
+        let window = match mode {
+            AppMode::Shell => self.shell_ui,
+            AppMode::Widgets => self.widgets_ui,
+            AppMode::Layout => self.layout_ui,
+            AppMode::Slides => self.slides_ui,
+        };
+        self.window = window;
+        self.ui = WidgetRef::new_from_ptr(cx, window);
+        if let Some(width) = self.window_width {
+            self.set_window_width(cx, width);
+        }
+        if self.debug_layout {
+            self.set_debug_layout(cx, true);
+        }
+    }
+
+    // Applies the layouts of the responsive views for a window of the given width. In
+    // `AppMode::Shell` every page has its own copies, the hidden ones included.
+    pub fn set_window_width(&mut self, cx: &mut Cx, width: f64) {
+        self.window_width = Some(width);
+        let roots: Vec<WidgetRef> = match self.mode {
+            AppMode::Shell => ShellPage::ALL.iter().map(|page| self.ui.widget(page.page_id())).collect(),
+            _ => vec![self.ui.clone()],
+        };
+        for root in roots {
+            for path in RESPONSIVE_VIEWS {
+                root.responsive_view(path).set_width(cx, width);
+            }
+        }
+    }
+
+    pub fn debug_layout(&self) -> bool {
+        self.debug_layout
+    }
+
+    // Shows or hides the bounds, ids, padding and margin of the widgets, see `debug_layout.rs`.
+    pub fn set_debug_layout(&mut self, cx: &mut Cx, enabled: bool) {
+        self.debug_layout = enabled;
+        let tree = if enabled {self.layout_tree(cx)} else {Vec::new()};
+        self.ui.layout_overlay(id!(debug_overlay)).set_tree(cx, tree);
+    }
+
+    // The named widgets of the window as a tree, without the debug overlay itself.
+    pub fn layout_tree(&self, cx: &mut Cx) -> Vec<LayoutNode> {
+        match self.window {
+            Some(window) => layout_tree(cx, &self.ui, window, &[live_id!(debug_overlay)]),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn ui(&self) -> &WidgetRef {
+        &self.ui
+    }
+
+    // The part of `ui` the demo widgets are looked up in: the page shown in `AppMode::Shell`,
+    // whose pages repeat the ids of the demos, and the whole window otherwise.
+    pub fn demo_ui(&self) -> WidgetRef {
+        match self.mode {
+            AppMode::Shell => self.ui.widget(self.page.page_id()),
+            _ => self.ui.clone(),
+        }
+    }
+
+    pub fn page(&self) -> ShellPage {
+        self.page
+    }
+
+    // Shows the given page of the navigation shell and hides the others.
+    pub fn set_page(&mut self, cx: &mut Cx, page: ShellPage) {
+        let items = self.demo_ui().drop_down(id!(items_dropdown.dropdown)).items();
+        self.page = page;
+        for other in ShellPage::ALL {
+            let selected = if other == page {1.0} else {0.0};
+            self.ui.view(other.page_id()).set_visible(other == page);
+            self.ui.button(other.tab_id()).apply_over(cx, live!{
+                draw_bg: {selected: (selected)}
+            });
+        }
+        self.ui.redraw(cx);
+        self.show_state(cx, items);
+    }
+
+    // Shows the app state in the widgets of the page shown, whose copies of the demos keep their
+    // own widget state, with `items` in `items_dropdown`.
+    fn show_state(&mut self, cx: &mut Cx, items: Vec<DropDownItem>) {
+        let ui = self.demo_ui();
+        self.update_transform_items(cx);
+        self.set_items(cx, items);
+        Self::data_bind(self.store.data_to_widgets(cx, &ui));
+        ui.check_box(id!(number_field)).set_selected(cx, self.number_field);
+        self.set_number_field(cx, self.number_field);
+        self.set_layout_section(cx, self.layout_section);
+        self.update_input_label();
+        self.update_notes_stats();
+    }
+
+    pub fn layout_section(&self) -> LayoutSection {
+        self.layout_section
+    }
+
+    // Shows the given section of `LayoutView` and hides the others.
+    pub fn set_layout_section(&mut self, cx: &mut Cx, section: LayoutSection) {
+        self.layout_section = section;
+        let ui = self.demo_ui();
+        for other in LayoutSection::ALL {
+            let selected = if other == section {1.0} else {0.0};
+            ui.view(other.section_id()).set_visible(other == section);
+            ui.button(other.tab_id()).apply_over(cx, live!{
+                draw_bg: {selected: (selected)}
+            });
+        }
+        ui.redraw(cx);
+    }
+
+    // Sets the file the bound values are saved to on exit and restored from on startup. `None`
+    // disables both.
+    pub fn set_state_file(&mut self, state_file: Option<PathBuf>) {
+        self.state_file = state_file;
+    }
+
+    pub fn store(&self) -> &DataBindingStore {
+        &self.store
+    }
+
+    pub fn reactor(&self) -> &Reactor<App> {
+        &self.reactor
+    }
+
+    pub fn data_bind(mut db: DataBindingMap) {
+        for (data_id, widget_path) in BINDINGS.iter().copied() {
+            db.bind(data_id, &[widget_path]);
+        }
+    }
+
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    // Reverts the last edit. Returns false if there was nothing to undo.
+    pub fn undo(&mut self, cx: &mut Cx) -> bool {
+        match self.history.undo() {
+            Some(changes) => {
+                self.restore(cx, changes.into_iter().map(|change| (change.field, change.old)));
+                true
+            }
+            None => false
+        }
+    }
+
+    // Reapplies the last undone edit. Returns false if there was nothing to redo.
+    pub fn redo(&mut self, cx: &mut Cx) -> bool {
+        match self.history.redo() {
+            Some(changes) => {
+                self.restore(cx, changes.into_iter().map(|change| (change.field, change.new)));
+                true
+            }
+            None => false
+        }
+    }
+
+    // Writes values from the history to the store and updates everything that depends on them.
+    fn restore(&mut self, cx: &mut Cx, values: impl Iterator<Item = (Vec<LiveId>, LiveValue)>) {
+        for (field, value) in values {
+            self.store.nodes.write_field_value(&field, value);
+        }
+        let ui = self.demo_ui();
+        Self::data_bind(self.store.data_to_widgets(cx, &ui));
+        // Setting widgets from data emits no actions, so update the dependents directly.
+        ui.validated_input(id!(input_sample)).validate(cx);
+        self.update_input_label();
+        ui.label(id!(label_input)).redraw(cx);
+    }
+
+    // Writes the current values of the fields with history to the store, so the first edit of
+    // each has a previous value to undo to. Fields restored from the state file, and widgets that
+    // aren't part of the current mode, are skipped.
+    fn seed_history(&mut self) {
+        for field in HISTORY_FIELDS {
+            if self.store.nodes.read_field_value(field).is_some() {
+                continue;
+            }
+            if let Some(value) = self.widget_value(field) {
+                self.store.nodes.write_field_value(field, value);
+            }
+        }
+        self.history.clear();
+    }
+
+    // The value the widget bound to a field with history currently shows.
+    fn widget_value(&self, field: &[LiveId]) -> Option<LiveValue> {
+        let (_, widget_path) = BINDINGS.iter().find(|(data_id, _)| *data_id == field)?;
+        let widget = self.demo_ui().widget(widget_path);
+        if widget.is_empty() {
+            return None;
+        }
+        if field == id!(counter) {
+            Some(LiveValue::Float64(widget.as_counter_widget().value()))
+        } else if field == id!(input_sample) {
+            Some(LiveValue::String(widget.text().into()))
+        } else if field == id!(my_dropdown) {
+            widget.as_drop_down().selected_value().map(LiveValue::Id)
+        } else {
+            None
+        }
+    }
+
+    fn history_values(&self) -> Vec<Option<LiveValue>> {
+        HISTORY_FIELDS
+            .iter()
+            .map(|field| self.store.nodes.read_field_value(field).cloned())
+            .collect()
+    }
+
+    // Records the fields with history that changed since `before` was taken.
+    fn record_history(&mut self, before: Vec<Option<LiveValue>>) {
+        let changes = HISTORY_FIELDS
+            .iter()
+            .zip(before)
+            .filter_map(|(field, old)| {
+                let new = self.store.nodes.read_field_value(field)?.clone();
+                Some(Change::new(field, old?, new))
+            })
+            .collect();
+        self.history.push(changes);
+    }
+
+    // Binds `input_sample` to the counter as a number field, or unbinds it.
+    pub fn set_number_field(&mut self, cx: &mut Cx, number_field: bool) {
+        self.number_field = number_field;
+        let ui = self.demo_ui();
+        let counter = ui.counter_widget(id!(counter));
+        let range = number_field.then(|| {
+            let (min, max) = counter.range();
+            (min, max, counter.precision())
+        });
+        ui.validated_input(id!(input_sample)).set_numeric_range(cx, range);
+        if number_field {
+            let text = counter.value_text();
+            self.store.nodes.write_field_value(id!(input_sample), LiveValue::String(text.into()));
+            Self::data_bind(self.store.data_to_widgets(cx, &ui));
+            ui.validated_input(id!(input_sample)).validate(cx);
+            self.update_input_label();
+            ui.label(id!(label_input)).redraw(cx);
+        }
+    }
+
+    // Copies a number typed into `input_sample` to the counter, or a counter change to the text,
+    // in the store. Runs between reading the widgets into the store and writing the store back,
+    // so both sides change in the same history entry and setting them emits no new actions.
+    fn sync_number_field(&mut self, actions: &Actions) {
+        if !self.number_field {
+            return;
+        }
+        let ui = self.demo_ui();
+        let counter = ui.counter_widget(id!(counter));
+        if let Some(text) = ui.text_input(id!(input_sample.input)).changed(actions) {
+            // Invalid numbers are left in the input, where the validator shows the error.
+            // Rounded as the counter rounds it, which is also what the validator checks.
+            let (min, max) = counter.range();
+            if let Ok(value) = text.trim().parse::<f64>() {
+                let value = round_to(value, counter.precision());
+                if (min..=max).contains(&value) {
+                    self.store.nodes.write_field_value(id!(counter), LiveValue::Float64(value));
+                }
+            }
+        } else if counter.changed(actions).is_some() {
+            let text = counter.value_text();
+            self.store.nodes.write_field_value(id!(input_sample), LiveValue::String(text.into()));
+        }
+    }
+
+    // App-wide keyboard shortcuts: undo/redo, and the counter shortcuts. Ctrl is Cmd on macOS.
+    fn handle_shortcut(&mut self, cx: &mut Cx, ke: &KeyEvent) {
+        let command = ke.modifiers.control || ke.modifiers.logo;
+        // A focused text input undoes its own edits, so the global history is left alone.
+        let undo_enabled = self.key_focus.is_empty();
+        match ke.key_code {
+            KeyCode::KeyZ if command && ke.modifiers.shift => {
+                if undo_enabled {
+                    self.redo(cx);
+                }
+            }
+            KeyCode::KeyZ if command => {
+                if undo_enabled {
+                    self.undo(cx);
+                }
+            }
+            KeyCode::KeyY if ke.modifiers.control => {
+                if undo_enabled {
+                    self.redo(cx);
+                }
+            }
+            KeyCode::F12 => {
+                self.set_debug_layout(cx, !self.debug_layout);
+            }
+            _ => {
+                if self.key_focus.is_empty() {
+                    if let Some(command) = self.counter_shortcuts.command(ke) {
+                        self.run_counter_command(cx, command);
+                    }
+                }
+            }
+        }
+    }
+
+    fn run_counter_command(&mut self, cx: &mut Cx, command: CounterCommand) {
+        let counter = self.demo_ui().counter_widget(id!(counter));
+        match command {
+            CounterCommand::Increment => counter.step(cx, 1),
+            CounterCommand::Decrement => counter.step(cx, -1),
+            CounterCommand::PageUp => counter.page(cx, 1),
+            CounterCommand::PageDown => counter.page(cx, -1),
+            CounterCommand::Reset => counter.reset(cx),
+        }
+    }
+
+    // Merges the counter steps of one press of a repeat button into a single undo entry.
+    fn update_history_group(&mut self, actions: &Actions) {
+        for action in actions {
+            if let Some(action) = action.as_widget_action() {
+                match action.cast() {
+                    RepeatButtonAction::Started => self.history.begin_group(),
+                    RepeatButtonAction::Stopped => self.history.end_group(),
+                    RepeatButtonAction::None => ()
+                }
+            }
+        }
+    }
+
+    // Whether any of the bound widgets emitted one of the actions.
+    fn bound_widget_changed(&self, actions: &Actions) -> bool {
+        BINDINGS.iter().any(|(_, widget_path)| {
+            actions.find_widget_action(self.demo_ui().widget(widget_path).widget_uid()).is_some()
+        })
+    }
+
+    // Registers the updates that depend on the actions of other widgets, so each one only runs
+    // when its source changed.
+    fn subscribe_updates(&mut self) {
+        self.reactor.clear();
+        self.reactor.subscribe(
+            "input label",
+            |app, actions| app.demo_ui().text_input(id!(input_sample.input)).changed(actions).is_some(),
+            |app, _cx| app.update_input_label(),
+            &[id!(label_input)],
+        );
+        self.reactor.subscribe(
+            "notes stats",
+            |app, actions| app.demo_ui().text_input(id!(notes.input)).changed(actions).is_some(),
+            |app, _cx| app.update_notes_stats(),
+            &[id!(notes_stats)],
+        );
+        self.reactor.subscribe(
+            "transform",
+            |app, actions| app.demo_ui().drop_down(id!(my_dropdown.dropdown)).changed(actions).is_some(),
+            |app, _cx| app.update_input_label(),
+            &[id!(label_input)],
+        );
+        self.reactor.subscribe(
+            "add item",
+            |app, actions| app.demo_ui().button(id!(add_item_button)).clicked(actions),
+            |app, cx| {
+                let mut items = app.demo_ui().drop_down(id!(items_dropdown.dropdown)).items();
+                items.insert(0, DropDownItem::from_label(format!("Item {}", items.len() + 1)));
+                app.set_items(cx, items);
+            },
+            &[id!(items_dropdown)],
+        );
+        self.reactor.subscribe(
+            "number field",
+            |app, actions| app.number_field && app.demo_ui().counter_widget(id!(counter)).changed(actions).is_some(),
+            |app, cx| {
+                // The text was set from the store, which doesn't validate it.
+                app.demo_ui().validated_input(id!(input_sample)).validate(cx);
+                app.update_input_label();
+            },
+            &[id!(input_sample), id!(label_input)],
+        );
+    }
+
+    // The item selected in `selection_dropdown`, if the user picked one or it was restored.
+    pub fn selection(&self) -> Option<Selection> {
+        read_enum(&self.store, id!(selection))
+    }
+
+    pub fn set_selection(&mut self, cx: &mut Cx, selection: Selection) {
+        // Update the store as well, so the binding doesn't revert the dropdown on the next actions.
+        self.store.nodes.write_field_value(id!(selection), LiveValue::Id(selection.live_id()));
+        self.demo_ui().drop_down(id!(selection_dropdown.dropdown)).set_selected_enum(cx, selection);
+    }
+
+    // The id of the transform selected in `my_dropdown`, `identity` until the items are set.
+    pub fn transform(&self) -> LiveId {
+        let dropdown = self.demo_ui().drop_down(id!(my_dropdown.dropdown));
+        dropdown.selected_value().unwrap_or(live_id!(identity))
+    }
+
+    // Replaces the items of `items_dropdown`. The selected item stays selected if it is still in
+    // the list.
+    pub fn set_items(&mut self, cx: &mut Cx, items: Vec<DropDownItem>) {
+        self.demo_ui().drop_down(id!(items_dropdown.dropdown)).set_items(cx, items);
+    }
+
+    // Adds a transform to `my_dropdown`, or replaces the one with the same id.
+    pub fn register_transform(&mut self, cx: &mut Cx, transform: Box<dyn TextTransform>) {
+        self.transforms.register(transform);
+        self.update_transform_items(cx);
+        self.update_input_label();
+        self.demo_ui().label(id!(label_input)).redraw(cx);
+    }
+
+    fn update_transform_items(&mut self, cx: &mut Cx) {
+        let dropdown = self.demo_ui().drop_down(id!(my_dropdown.dropdown));
+        dropdown.set_items(cx, self.transforms.items());
+    }
+
+    fn update_notes_stats(&mut self) {
+        let ui = self.demo_ui();
+        let stats = text_stats(&ui.text_input(id!(notes.input)).text());
+        ui.label(id!(notes_stats)).set_text(&format!(
+            "{} lines, {} words, {} characters",
+            stats.lines, stats.words, stats.chars
+        ));
+    }
+
+    fn update_input_label(&mut self) {
+        let text = self.demo_ui().text_input(id!(input_sample.input)).text();
+        let value = self.transforms.apply(self.transform(), &text);
+        // log!("value={}", value);
+        let label = self.demo_ui().label(id!(label_input));
+        label.set_text(&format!("Input: {}",  value));
     }
 }
 
+// The views whose layout depends on the window width, see `responsive.rs`.
+const RESPONSIVE_VIEWS: &[&[LiveId]] = &[id!(ButtonView), id!(InputView)];
+
+// The store fields and the widgets they are bound to.
+const BINDINGS: &[(&[LiveId], &[LiveId])] = &[
+    (id!(counter), id!(counter)),
+    (id!(input_sample), id!(input_sample.input)),
+    (id!(my_dropdown), id!(my_dropdown.dropdown)),
+    (id!(selection), id!(selection_dropdown.dropdown)),
+    (id!(instrument), id!(instrument_combo)),
+    (id!(my_multi_select), id!(my_multi_select.dropdown)),
+];
+
+// The store fields whose edits can be undone.
+const HISTORY_FIELDS: &[&[LiveId]] = &[
+    id!(counter),
+    id!(input_sample),
+    id!(my_dropdown),
+];
+
 impl MatchEvent for App{
+    fn handle_startup(&mut self, cx: &mut Cx) {
+        if let Some(path) = &self.state_file {
+            if let Err(err) = load_store(&mut self.store, path) {
+                log!("Could not restore state from {}: {}", path.display(), err);
+            }
+        }
+        // The transforms are set first, so the restored one can be selected.
+        self.update_transform_items(cx);
+        let ui = self.demo_ui();
+        Self::data_bind(self.store.data_to_widgets(cx, &ui));
+        self.seed_history();
+        self.update_input_label();
+        self.update_notes_stats();
+
+        let items = (1..=3).map(|i| DropDownItem::from_label(format!("Item {}", i))).collect();
+        self.set_items(cx, items);
+    }
+
+    fn handle_shutdown(&mut self, _cx: &mut Cx) {
+        if let Some(path) = &self.state_file {
+            if let Err(err) = save_store(&self.store, path) {
+                log!("Could not save state to {}: {}", path.display(), err);
+            }
+        }
+    }
+
     fn handle_actions(&mut self, cx: &mut Cx, actions:&Actions) {
-        // Get a reference to our button from the frame, and check if one of the actions returned by
-        // the frame was a notification that the button was clicked.
-        if self.ui.button(id!(button1)).clicked(&actions) {
-            // Increment the counter.
-            self.counter += 1;
-            // log!("counter={}", self.counter);
-            
-            // Get a reference to our label from the frame, update its text, and schedule a redraw
-            // for it.
-            let label = self.ui.label(id!(label1));
-            label.set_text_and_redraw(cx,&format!("Label: {}", self.counter));
+        // Switch pages when one of the navigation shell's tabs is clicked.
+        if self.mode == AppMode::Shell {
+            for page in ShellPage::ALL {
+                if page != self.page && self.ui.button(page.tab_id()).clicked(&actions) {
+                    self.set_page(cx, page);
+                }
+            }
         }
 
-        if self.ui.button(id!(button2)).clicked(&actions) {
-            // Decrement the counter.
-            if self.counter >= 1 {
-                self.counter -= 1;
+        // Switch the section of `LayoutView`, in whichever window shows it.
+        for section in LayoutSection::ALL {
+            if section != self.layout_section && self.demo_ui().button(section.tab_id()).clicked(&actions) {
+                self.set_layout_section(cx, section);
             }
-            // log!("counter={}", self.counter);
-            
-            // Get a reference to our label from the frame, update its text, and schedule a redraw
-            // for it.
-            let label = self.ui.label(id!(label1));
-            label.set_text_and_redraw(cx,&format!("Label: {}", self.counter));
         }
 
-        let value = self.ui.text_input(id!(input_sample)).text();
-        // log!("value={}", value);
-        let label =self.ui.label(id!(label_input));
-        label.set_text_and_redraw(cx, &format!("Input: {}",  value));
+        self.update_history_group(actions);
+
+        if let Some(number_field) = self.demo_ui().check_box(id!(number_field)).changed(&actions) {
+            self.set_number_field(cx, number_field);
+        }
+
+        if self.demo_ui().button(id!(undo_button)).clicked(&actions) {
+            self.undo(cx);
+        }
+        if self.demo_ui().button(id!(redo_button)).clicked(&actions) {
+            self.redo(cx);
+        }
 
-        let ui = self.ui.clone();
-        let mut drop_db = DataBindingStore::new();
-        self.data_bind(drop_db.widgets_to_data(cx, &actions, &ui));
-        self.data_bind(drop_db.data_to_widgets(cx, &ui));
+        // Only propagate bound values when a bound widget changed, instead of on every batch.
+        let ui = self.demo_ui();
+        if self.bound_widget_changed(actions) {
+            let before = self.history_values();
+            Self::data_bind(self.store.widgets_to_data(cx, &actions, &ui));
+            self.sync_number_field(actions);
+            self.record_history(before);
+            Self::data_bind(self.store.data_to_widgets(cx, &ui));
+        }
+
+        // The reactor is taken out while it runs, as its updates need the whole app.
+        let mut reactor = std::mem::take(&mut self.reactor);
+        reactor.dispatch(self, cx, &ui, actions);
+        self.reactor = reactor;
     }
 }
 
 impl AppMain for App {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event) {
+        match event {
+            Event::Draw(_) => self.reactor.end_frame(),
+            Event::KeyDown(ke) => self.handle_shortcut(cx, ke),
+            Event::KeyFocus(ke) | Event::KeyFocusLost(ke) => self.key_focus = ke.focus,
+            // The app has a single window, so every resize is one of `ui`.
+            Event::WindowGeomChange(ev) => self.set_window_width(cx, ev.new_geom.inner_size.x),
+            _ => ()
+        }
         self.match_event(cx, event);
         self.ui.handle_event(cx, event, &mut Scope::empty());
     }
@@ -0,0 +1,321 @@
+use makepad_widgets::*;
+use crate::popup_list::{DrawHover, PopupItem, PopupList, PopupListAction};
+
+// A searchable variant of `DropDown`. The popup starts with a filter `TextInput`; typing in it
+// narrows the items to those matching the filter, first by substring and then fuzzily (the
+// characters of the filter appearing in order). The arrow keys move the highlight, wrapping
+// around at either end and scrolling the list to keep it in view, Enter selects the highlighted
+// item and Escape closes the popup.
+//
+// Items are declared with `values` and `labels` like for a `DropDown`, and a selection is
+// reported with the same `DropDownAction::Select` action, so `DropDownRef::changed` and data
+// binding work unchanged:
+//
+//     my_combo = <ComboBox> {
+//         values: [apple, pear, plum]
+//         labels: ["Apple", "Pear", "Plum"]
+//     }
+//
+// `FishComboBox` in `app.rs` styles it like `FishDropDown`.
+live_design!{
+    import makepad_widgets::base::*;
+    import makepad_widgets::theme_desktop_dark::*;
+    import makepad_draw::shader::std::*;
+    import crate::popup_list::*;
+
+    ComboBox = {{ComboBox}} {
+        width: Fit,
+        height: Fit,
+        padding: {top: 8.0, right: 16.0, bottom: 8.0, left: 8.0}
+
+        draw_bg: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.rect(0, 0, self.rect_size.x, self.rect_size.y);
+                sdf.fill(mix(#xFFFFFF00, #xFFFFFF10, self.hover));
+                // triangle
+                let c = vec2(self.rect_size.x - 10.0, self.rect_size.y * 0.5)
+                let sz = 2.5;
+                sdf.move_to(c.x - sz, c.y - sz);
+                sdf.line_to(c.x + sz, c.y - sz);
+                sdf.line_to(c.x, c.y + sz * 0.75);
+                sdf.close_path();
+                sdf.fill(mix(#FFFA, #FFFF, max(self.hover, self.selected)));
+                return sdf.result
+            }
+        }
+
+        draw_text: {
+            color: #xFFFFFFCC
+        }
+
+        popup: <PopupList> {}
+
+        filter_input: <TextInput> {
+            width: Fill,
+            height: Fit,
+            margin: {bottom: 4.0}
+            draw_bg: {
+                color: #333
+            }
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct ComboBox {
+    // The closed state: the selected label and a triangle.
+    #[redraw] #[live] draw_bg: DrawHover,
+    #[live] draw_text: DrawText,
+    #[walk] walk: Walk,
+    #[layout] layout: Layout,
+
+    // The popup: the filter input above the list of matching items.
+    #[live] popup: PopupList,
+    #[live] filter_input: TextInput,
+
+    #[live] labels: Vec<String>,
+    #[live] values: Vec<LiveValue>,
+    #[live] selected_item: usize,
+
+    #[rust] is_open: bool,
+    // Set when the popup opens, to give the filter input key focus once it is drawn.
+    #[rust] focus_filter: bool,
+    // Indices of the items matching the filter, best match first.
+    #[rust] matches: Vec<usize>,
+    // Index into `matches` of the highlighted item.
+    #[rust] highlighted: usize,
+}
+
+impl Widget for ComboBox {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if self.is_open {
+            self.handle_popup_event(cx, event, scope);
+        }
+
+        match event.hits(cx, self.draw_bg.area()) {
+            Hit::FingerHoverIn(_) => {
+                cx.set_cursor(MouseCursor::Hand);
+                self.draw_bg.hover = 1.0;
+                self.draw_bg.redraw(cx);
+            }
+            Hit::FingerHoverOut(_) => {
+                self.draw_bg.hover = 0.0;
+                self.draw_bg.redraw(cx);
+            }
+            Hit::FingerDown(_) => {
+                if self.is_open {
+                    self.close(cx);
+                } else {
+                    self.open(cx);
+                }
+            }
+            _ => ()
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.draw_bg.selected = if self.is_open {1.0} else {0.0};
+        self.draw_bg.begin(cx, walk, self.layout);
+        let label = self.labels.get(self.selected_item).map(String::as_str).unwrap_or("");
+        self.draw_text.draw_walk(cx, Walk::fit(), Align::default(), label);
+        self.draw_bg.end(cx);
+
+        if self.is_open {
+            self.draw_popup(cx, scope);
+        }
+        DrawStep::done()
+    }
+
+    fn widget_to_data(&self, _cx: &mut Cx, actions: &Actions, nodes: &mut LiveNodeVec, path: &[LiveId]) -> bool {
+        match actions.find_widget_action_cast(self.widget_uid()) {
+            DropDownAction::Select(_, value) => {
+                nodes.write_field_value(path, value.clone());
+                true
+            }
+            _ => false
+        }
+    }
+
+    fn data_to_widget(&mut self, cx: &mut Cx, nodes: &[LiveNode], path: &[LiveId]) {
+        if let Some(value) = nodes.read_field_value(path) {
+            if let Some(index) = self.values.iter().position(|v| v == value) {
+                if self.selected_item != index {
+                    self.selected_item = index;
+                    self.redraw(cx);
+                }
+            }
+        }
+    }
+}
+
+impl ComboBox {
+    fn open(&mut self, cx: &mut Cx) {
+        self.is_open = true;
+        self.focus_filter = true;
+        self.filter_input.set_text("");
+        self.update_matches("");
+        // Start on the selected item.
+        self.popup.reset();
+        let highlighted = self.matches.iter().position(|&i| i == self.selected_item).unwrap_or(0);
+        self.highlight(highlighted);
+        self.redraw(cx);
+    }
+
+    fn close(&mut self, cx: &mut Cx) {
+        self.is_open = false;
+        // The filter isn't drawn while closed, so it gives up the key focus.
+        if cx.has_key_focus(self.filter_input.area()) {
+            cx.set_key_focus(Area::Empty);
+        }
+        self.popup.redraw(cx);
+        self.redraw(cx);
+    }
+
+    fn highlight(&mut self, highlighted: usize) {
+        self.highlighted = highlighted;
+        self.popup.scroll_to(highlighted);
+    }
+
+    fn select(&mut self, cx: &mut Cx, scope: &mut Scope, index: usize) {
+        self.selected_item = index;
+        let value = self.values.get(index).cloned().unwrap_or(LiveValue::None);
+        cx.widget_action(self.widget_uid(), &scope.path, DropDownAction::Select(index, value));
+        self.close(cx);
+    }
+
+    fn update_matches(&mut self, filter: &str) {
+        let mut scored: Vec<(usize, usize)> = self.labels
+            .iter()
+            .enumerate()
+            .filter_map(|(index, label)| match_score(label, filter).map(|score| (score, index)))
+            .collect();
+        scored.sort();
+        self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        self.popup.reset();
+        self.highlighted = 0;
+    }
+
+    fn handle_popup_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let filter_uid = self.filter_input.widget_uid();
+        let actions = cx.capture_actions(|cx| self.filter_input.handle_event(cx, event, scope));
+        if let TextInputAction::Change(filter) = actions.find_widget_action_cast(filter_uid) {
+            self.update_matches(&filter);
+            self.redraw(cx);
+        }
+
+        if let Event::KeyDown(ke) = event {
+            let count = self.matches.len();
+            match ke.key_code {
+                KeyCode::ArrowDown if count > 0 => {
+                    self.highlight((self.highlighted + 1) % count);
+                    self.redraw(cx);
+                }
+                KeyCode::ArrowUp if count > 0 => {
+                    self.highlight((self.highlighted + count - 1) % count);
+                    self.redraw(cx);
+                }
+                KeyCode::ReturnKey => {
+                    if let Some(&index) = self.matches.get(self.highlighted) {
+                        self.select(cx, scope, index);
+                    }
+                }
+                KeyCode::Escape => self.close(cx),
+                _ => ()
+            }
+            return;
+        }
+
+        match self.popup.handle_event(cx, event, self.draw_bg.area()) {
+            PopupListAction::HoverIn(row) => {
+                self.highlighted = row;
+                self.redraw(cx);
+            }
+            PopupListAction::Clicked(row) => {
+                let index = self.matches[row];
+                self.select(cx, scope, index);
+            }
+            // Clicking anywhere outside of the popup and the closed state closes the popup.
+            PopupListAction::ClickedOutside => self.close(cx),
+            PopupListAction::HoverOut(_) | PopupListAction::None => ()
+        }
+    }
+
+    fn draw_popup(&mut self, cx: &mut Cx2d, scope: &mut Scope) {
+        self.popup.begin(cx, self.draw_bg.area().rect(cx));
+
+        let filter_walk = self.filter_input.walk(cx);
+        let _ = self.filter_input.draw_walk(cx, scope, filter_walk);
+        if self.focus_filter {
+            self.focus_filter = false;
+            cx.set_key_focus(self.filter_input.area());
+        }
+
+        let items: Vec<PopupItem> = self.matches
+            .iter()
+            .enumerate()
+            .map(|(row, &index)| PopupItem {
+                label: &self.labels[index],
+                highlighted: row == self.highlighted,
+                selected: index == self.selected_item,
+            })
+            .collect();
+        self.popup.draw_items(cx, &items);
+        self.popup.end(cx);
+    }
+}
+
+// How well `label` matches `filter`, lower is better. A case-insensitive substring match scores
+// its position in the label. Otherwise, if the characters of the filter appear in the label in
+// order, the match is fuzzy and scores after all substring matches, by how spread out it is.
+pub fn match_score(label: &str, filter: &str) -> Option<usize> {
+    if filter.is_empty() {
+        return Some(0);
+    }
+    let label = label.to_lowercase();
+    let filter = filter.to_lowercase();
+    if let Some(pos) = label.find(&filter) {
+        return Some(pos);
+    }
+
+    let mut filter_chars = filter.chars().peekable();
+    let mut first = None;
+    let mut last = 0;
+    for (pos, c) in label.char_indices() {
+        if filter_chars.peek() == Some(&c) {
+            filter_chars.next();
+            first.get_or_insert(pos);
+            last = pos;
+        }
+    }
+    if filter_chars.peek().is_some() {
+        return None;
+    }
+    Some(label.len() + last - first.unwrap_or(0))
+}
+
+impl ComboBoxRef {
+    pub fn selected_item(&self) -> usize {
+        if let Some(inner) = self.borrow() {
+            inner.selected_item
+        } else {
+            0
+        }
+    }
+
+    pub fn set_selected_item(&self, cx: &mut Cx, item: usize) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.selected_item = item.min(inner.labels.len().saturating_sub(1));
+            inner.redraw(cx);
+        }
+    }
+
+    // The index of the selected item, if it was changed by one of the given actions.
+    pub fn changed(&self, actions: &Actions) -> Option<usize> {
+        if let DropDownAction::Select(index, _) = actions.find_widget_action_cast(self.widget_uid()) {
+            Some(index)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,359 @@
+use makepad_widgets::*;
+
+// A counter with an increment button (`button1`), a label showing the value (`label1`) and a
+// decrement button (`button2`). The value, its bounds, the step, what happens when a step goes
+// past the bounds and the label format are live properties, so several counters can be dropped
+// into any view and configured from the DSL:
+//
+//     my_counter = <CounterWidget> {
+//         value: 10, min: 0, max: 100, step: 5, page_step: 25,
+//         overflow: Wrap,
+//         format: "Volume: {}"
+//     }
+//     gain = <CounterWidget> {
+//         value: 0.0, min: -1.0, max: 1.0, step: 0.05, precision: 2,
+//         overflow: Reject,
+//         format: "Gain: {} dB"
+//     }
+//
+// Values are `f64`, shown with `precision` decimals and rounded to them after every step. With
+// `overflow: Reject`, a step past the bounds is ignored and the label shakes instead.
+//
+// Both buttons are `RepeatButton`s (see `repeat_button.rs`), so holding one down keeps stepping
+// the value at an accelerating rate. Their text is set on the inner `button`.
+//
+// Besides the buttons, the value can be stepped from Rust with `CounterWidgetRef::step`, `page` and
+// `reset`, e.g. for keyboard shortcuts. Every change made through the buttons or those methods is
+// reported with a `CounterAction::Changed` action. The value can also be bound to a
+// `DataBindingStore` as a `Float64` field.
+live_design!{
+    import makepad_widgets::base::*;
+    import makepad_widgets::theme_desktop_dark::*;
+    import crate::repeat_button::*;
+
+    CounterWidget = {{CounterWidget}} {
+        width: Fit,
+        height: Fit,
+        flow: Down,
+        spacing: 20,
+        align: {
+            x: 0.5,
+            y: 0.5
+        }
+        format: "{}"
+
+        button1 = <RepeatButton> {
+            button = { text: "+" }
+        }
+
+        label1 = <Label> {
+            width: 100,
+            draw_text: {
+                color: #f
+            },
+            text: "0"
+        }
+
+        button2 = <RepeatButton> {
+            button = { text: "-" }
+        }
+    }
+}
+
+// What a step past `min` or `max` does.
+#[derive(Clone, Copy, Debug, PartialEq, Live, LiveHook)]
+#[live_ignore]
+pub enum OverflowPolicy {
+    // Stop at the bound.
+    #[pick] Clamp,
+    // Continue from the other bound, e.g. 9 + 1 gives 0 for `0..=9`.
+    Wrap,
+    // Leave the value unchanged.
+    Reject,
+}
+
+// Whether `bound` limits the value. `f64::MIN` and `f64::MAX`, the defaults of `min` and `max`,
+// stand for no bound.
+pub fn is_bounded(bound: f64) -> bool {
+    bound.is_finite() && bound != f64::MIN && bound != f64::MAX
+}
+
+// Rounds `value` to `precision` decimals, as the counter does with its value. Also used to check
+// numbers typed for a counter, see `Validator::RoundedRange`.
+pub fn round_to(value: f64, precision: usize) -> f64 {
+    let scale = 10f64.powi(precision.min(15) as i32);
+    let rounded = (value * scale).round() / scale;
+    if rounded.is_finite() {rounded} else {value}
+}
+
+impl OverflowPolicy {
+    // The value after stepping to `value`, or `None` if the step is rejected. `step` is the size of
+    // one step, which separates `max` from `min` when wrapping. Wrapping needs both bounds, without
+    // them the value is clamped.
+    pub fn apply(self, value: f64, min: f64, max: f64, step: f64) -> Option<f64> {
+        if (min..=max).contains(&value) {
+            return Some(value);
+        }
+        match self {
+            Self::Clamp => Some(value.clamp(min, max)),
+            Self::Wrap if is_bounded(min) && is_bounded(max) => {
+                let span = max - min + step.abs();
+                Some(min + (value - min).rem_euclid(span))
+            }
+            Self::Wrap => Some(value.clamp(min, max)),
+            Self::Reject => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum CounterAction {
+    // The value was changed by the user, carries the new value.
+    Changed(f64),
+    None,
+}
+
+// How far and for how long the label shakes when a step is rejected.
+const SHAKE_AMPLITUDE: f64 = 6.0;
+const SHAKE_DURATION: f64 = 0.4;
+
+#[derive(Live, Widget)]
+pub struct CounterWidget {
+    #[deref] view: View,
+
+    // The current value, kept within `min..=max`.
+    #[live] value: f64,
+    #[live(f64::MIN)] min: f64,
+    #[live(f64::MAX)] max: f64,
+    // How much a button press changes the value.
+    #[live(1.0)] step: f64,
+    // How much a page step changes the value.
+    #[live(10.0)] page_step: f64,
+    #[live] overflow: OverflowPolicy,
+    // The number of decimals shown.
+    #[live] precision: usize,
+    // The label text, with `{}` replaced by the value.
+    #[live] format: String,
+
+    // The value declared in the DSL, restored by `reset`.
+    #[rust] initial_value: f64,
+    // Whether the label is shaking, and when the current shake started.
+    #[rust] shaking: bool,
+    #[rust] shake_start: Option<f64>,
+    #[rust] next_frame: NextFrame,
+}
+
+impl LiveHook for CounterWidget {
+    // Checks the bounds whenever they are applied, also by `apply_over`, and clamps the value to
+    // them.
+    fn after_apply(&mut self, cx: &mut Cx, _from: ApplyFrom, _index: usize, _nodes: &[LiveNode]) {
+        if self.min.is_nan() || self.max.is_nan() {
+            log!("CounterWidget: the bounds {}..={} aren't numbers, they are ignored", self.min, self.max);
+            self.min = if self.min.is_nan() {f64::MIN} else {self.min};
+            self.max = if self.max.is_nan() {f64::MAX} else {self.max};
+        }
+        if self.min > self.max {
+            log!("CounterWidget: min {} is above max {}, the bounds are swapped", self.min, self.max);
+            std::mem::swap(&mut self.min, &mut self.max);
+        }
+        let value = self.round(self.value.clamp(self.min, self.max));
+        if value != self.value {
+            self.value = value;
+            self.update_label(cx);
+        }
+    }
+
+    fn after_new_from_doc(&mut self, cx: &mut Cx) {
+        self.value = self.round(self.value.clamp(self.min, self.max));
+        self.initial_value = self.value;
+        self.update_label(cx);
+    }
+}
+
+impl Widget for CounterWidget {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if self.shaking {
+            if let Some(ne) = self.next_frame.is_event(event) {
+                self.animate_shake(cx, ne.time);
+            }
+        }
+        self.view.handle_event(cx, event, scope);
+        self.widget_match_event(cx, event, scope);
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+
+    fn widget_to_data(&self, _cx: &mut Cx, actions: &Actions, nodes: &mut LiveNodeVec, path: &[LiveId]) -> bool {
+        match actions.find_widget_action_cast(self.widget_uid()) {
+            CounterAction::Changed(value) => {
+                nodes.write_field_value(path, LiveValue::Float64(value));
+                true
+            }
+            _ => false
+        }
+    }
+
+    fn data_to_widget(&mut self, cx: &mut Cx, nodes: &[LiveNode], path: &[LiveId]) {
+        // Integers are accepted as well, as saved by earlier versions.
+        let value = match nodes.read_field_value(path) {
+            Some(LiveValue::Float64(value)) => *value,
+            Some(LiveValue::Int64(value)) => *value as f64,
+            _ => return
+        };
+        if value != self.value {
+            self.set_value(cx, value);
+        }
+    }
+}
+
+impl WidgetMatchEvent for CounterWidget {
+    fn handle_actions(&mut self, cx: &mut Cx, actions: &Actions, scope: &mut Scope) {
+        if self.button(id!(button1)).clicked(actions) {
+            self.step_by(cx, &scope.path, self.step);
+        }
+        if self.button(id!(button2)).clicked(actions) {
+            self.step_by(cx, &scope.path, -self.step);
+        }
+    }
+}
+
+impl CounterWidget {
+    fn step_by(&mut self, cx: &mut Cx, path: &HeapLiveIdPath, delta: f64) {
+        let value = self.round(self.value + delta);
+        match self.overflow.apply(value, self.min, self.max, self.step) {
+            Some(value) => self.change_to(cx, path, value),
+            None => self.shake(cx),
+        }
+    }
+
+    // Sets the value and emits an action if it changed.
+    fn change_to(&mut self, cx: &mut Cx, path: &HeapLiveIdPath, value: f64) {
+        let value = self.round(value.clamp(self.min, self.max));
+        if value != self.value {
+            self.set_value(cx, value);
+            cx.widget_action(self.widget_uid(), path, CounterAction::Changed(value));
+        }
+    }
+
+    // Sets the value without emitting an action.
+    pub fn set_value(&mut self, cx: &mut Cx, value: f64) {
+        self.value = self.round(value.clamp(self.min, self.max));
+        self.update_label(cx);
+    }
+
+    // Rounds to the number of decimals shown, so steps like 0.1 don't accumulate errors.
+    fn round(&self, value: f64) -> f64 {
+        round_to(value, self.precision)
+    }
+
+    fn value_text(&self) -> String {
+        format!("{:.*}", self.precision, self.value)
+    }
+
+    fn update_label(&mut self, cx: &mut Cx) {
+        let value = self.value_text();
+        let text = if self.format.is_empty() {
+            value
+        } else {
+            self.format.replace("{}", &value)
+        };
+        self.label(id!(label1)).set_text_and_redraw(cx, &text);
+    }
+
+    fn shake(&mut self, cx: &mut Cx) {
+        self.shaking = true;
+        self.shake_start = None;
+        self.next_frame = cx.new_next_frame();
+    }
+
+    // Moves the label back and forth with a decaying amplitude, one step per frame.
+    fn animate_shake(&mut self, cx: &mut Cx, time: f64) {
+        let start = *self.shake_start.get_or_insert(time);
+        let progress = (time - start) / SHAKE_DURATION;
+        let offset = if progress < 1.0 {
+            self.next_frame = cx.new_next_frame();
+            SHAKE_AMPLITUDE * (1.0 - progress) * (progress * 6.0 * std::f64::consts::PI).sin().abs()
+        } else {
+            self.shaking = false;
+            self.shake_start = None;
+            0.0
+        };
+        self.label(id!(label1)).apply_over(cx, live!{
+            margin: {left: (offset)}
+        });
+        self.redraw(cx);
+    }
+
+}
+
+impl CounterWidgetRef {
+    pub fn value(&self) -> f64 {
+        if let Some(inner) = self.borrow() {
+            inner.value
+        } else {
+            0.0
+        }
+    }
+
+    // The value with `precision` decimals, without the label format.
+    pub fn value_text(&self) -> String {
+        self.borrow().map_or_else(String::new, |inner| inner.value_text())
+    }
+
+    // The bounds of the value.
+    pub fn range(&self) -> (f64, f64) {
+        self.borrow().map_or((f64::MIN, f64::MAX), |inner| (inner.min, inner.max))
+    }
+
+    // The number of decimals the value is rounded to.
+    pub fn precision(&self) -> usize {
+        self.borrow().map_or(0, |inner| inner.precision)
+    }
+
+    // Sets the value without emitting an action.
+    pub fn set_value(&self, cx: &mut Cx, value: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_value(cx, value);
+        }
+    }
+
+    // Changes the value by `steps` steps, as if a button was pressed that many times.
+    pub fn step(&self, cx: &mut Cx, steps: i64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            let delta = inner.step * steps as f64;
+            inner.step_by(cx, &HeapLiveIdPath::default(), delta);
+        }
+    }
+
+    // Changes the value by `pages` page steps.
+    pub fn page(&self, cx: &mut Cx, pages: i64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            let delta = inner.page_step * pages as f64;
+            inner.step_by(cx, &HeapLiveIdPath::default(), delta);
+        }
+    }
+
+    // Changes the value back to the one declared in the DSL.
+    pub fn reset(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            let value = inner.initial_value;
+            inner.change_to(cx, &HeapLiveIdPath::default(), value);
+        }
+    }
+
+    // Whether the label is shaking after a rejected step.
+    pub fn is_shaking(&self) -> bool {
+        self.borrow().is_some_and(|inner| inner.shaking)
+    }
+
+    // The new value, if the counter was changed by one of the given actions.
+    pub fn changed(&self, actions: &Actions) -> Option<f64> {
+        if let CounterAction::Changed(value) = actions.find_widget_action_cast(self.widget_uid()) {
+            Some(value)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use makepad_widgets::*;
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+// A layout debug overlay. While enabled, every named widget of the window is outlined, labeled
+// with its live id (`button1`, `RowView2`, `ColumnView3`, ...), and its padding and margin are
+// shaded: padding in green inside the outline, margin in orange outside of it.
+//
+// The overlay is a child of the window, after `body`, so it is drawn on top of everything else:
+//
+//     shell_ui: <Window> {
+//         body = <View> { ... }
+//         debug_overlay = <LayoutOverlay> {}
+//     }
+//
+// The rects and margins come from the widgets as they were last drawn, see `drawn_widgets`, so
+// they follow `apply_over`. The padding is the one declared in the DSL, unless the widget reports
+// its own through a layout inspector, which can also list the children it creates from Rust, see
+// `register_layout_inspector`. `App` toggles the overlay with F12, or enables it at startup with
+// `--debug-layout`.
+live_design!{
+    import makepad_widgets::base::*;
+    import makepad_widgets::theme_desktop_dark::*;
+    import makepad_draw::shader::std::*;
+
+    LayoutOverlay = {{LayoutOverlay}} {
+        draw_margin: {color: #xE8A04040}
+        draw_padding: {color: #x40E86040}
+        draw_outline: {
+            color: #xFF40A0CC
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.rect(0.5, 0.5, self.rect_size.x - 1.0, self.rect_size.y - 1.0);
+                sdf.stroke(self.color, 1.0);
+                return sdf.result
+            }
+        }
+        draw_label: {
+            text_style: {font_size: 7},
+            color: #xFF40A0
+        }
+    }
+}
+
+// A named widget of a window, and the named widgets under it.
+#[derive(Clone)]
+pub struct LayoutNode {
+    pub id: LiveId,
+    pub widget: WidgetRef,
+    // The padding of the widget's layout in the DSL.
+    pub padding: Padding,
+    pub children: Vec<LayoutNode>,
+}
+
+// What a widget reports about its layout beyond what the DSL says.
+#[derive(Default)]
+pub struct InspectedLayout {
+    // The padding the widget lays out its children with, if it can change at runtime.
+    pub padding: Option<Padding>,
+    // The children the widget creates from Rust, which aren't in the DSL.
+    pub children: Vec<LayoutNode>,
+}
+
+// Returns `None` for widgets of other types.
+pub type LayoutInspector = fn(&mut Cx, &WidgetRef) -> Option<InspectedLayout>;
+
+thread_local! {
+    // The inspectors, by the widget type they inspect.
+    static INSPECTORS: RefCell<HashMap<TypeId, LayoutInspector>> = RefCell::new(HashMap::new());
+}
+
+// Lets widgets of type `T` report their layout to the overlay, e.g. from `live_register`:
+//
+//     register_layout_inspector::<AlignmentMatrix>(alignment_matrix::inspect_layout);
+pub fn register_layout_inspector<T: 'static>(inspector: LayoutInspector) {
+    INSPECTORS.with(|inspectors| inspectors.borrow_mut().insert(TypeId::of::<T>(), inspector));
+}
+
+fn inspect_layout(cx: &mut Cx, widget: &WidgetRef) -> InspectedLayout {
+    let inspectors: Vec<LayoutInspector> = INSPECTORS.with(|inspectors| inspectors.borrow().values().copied().collect());
+    inspectors
+        .into_iter()
+        .find_map(|inspector| inspector(cx, widget))
+        .unwrap_or_default()
+}
+
+// The named widgets of the window `ui`, whose DSL object is at `ptr`, in document order. Instances
+// named `skip` are left out along with their children.
+//
+// Widgets don't list their children, so their ids are taken from the DSL. Each one is looked up
+// under its own parent, so that widgets with the same id in different parents are told apart.
+pub fn layout_tree(cx: &mut Cx, ui: &WidgetRef, ptr: LivePtr, skip: &[LiveId]) -> Vec<LayoutNode> {
+    let live_registry_rc = cx.live_registry.clone();
+    let live_registry = live_registry_rc.borrow();
+    let (nodes, index) = live_registry.ptr_to_nodes_index(ptr);
+    collect_nodes(nodes, index, ui, skip)
+}
+
+fn collect_nodes(nodes: &[LiveNode], index: usize, parent: &WidgetRef, skip: &[LiveId]) -> Vec<LayoutNode> {
+    let mut result = Vec::new();
+    let mut child = nodes.first_child(index);
+    while let Some(child_index) = child {
+        let node = &nodes[child_index];
+        if node.origin.prop_type() == LivePropType::Instance && node.value.is_open() && !skip.contains(&node.id) {
+            if node.id == LiveId(0) {
+                // Unnamed instances can't be looked up, their children are looked up under the
+                // closest named parent instead.
+                result.extend(collect_nodes(nodes, child_index, parent, skip));
+            } else {
+                let widget = parent.widget(&[node.id]);
+                if !widget.is_empty() {
+                    result.push(LayoutNode {
+                        id: node.id,
+                        padding: dsl_padding(nodes, child_index),
+                        children: collect_nodes(nodes, child_index, &widget, skip),
+                        widget,
+                    });
+                }
+            }
+        }
+        child = nodes.next_child(child_index);
+    }
+    result
+}
+
+// The node for a widget created from Rust out of the DSL object at `ptr`, for inspectors to
+// list.
+pub fn layout_node(cx: &mut Cx, id: LiveId, widget: WidgetRef, ptr: LivePtr) -> LayoutNode {
+    let live_registry_rc = cx.live_registry.clone();
+    let live_registry = live_registry_rc.borrow();
+    let (nodes, index) = live_registry.ptr_to_nodes_index(ptr);
+    LayoutNode {
+        id,
+        padding: dsl_padding(nodes, index),
+        children: collect_nodes(nodes, index, &widget, &[]),
+        widget,
+    }
+}
+
+// The `padding` of the DSL object at `index`, either one number for all sides or an object with
+// any of `left`, `top`, `right` and `bottom`.
+fn dsl_padding(nodes: &[LiveNode], index: usize) -> Padding {
+    let Some(padding_index) = nodes.child_by_name(index, live_id!(padding).as_field()) else {
+        return Padding::default();
+    };
+    if let Some(all) = nodes[padding_index].value.as_float() {
+        return Padding {left: all, top: all, right: all, bottom: all};
+    }
+    let side = |id: LiveId| {
+        nodes
+            .child_by_name(padding_index, id.as_field())
+            .and_then(|side_index| nodes[side_index].value.as_float())
+            .unwrap_or(0.0)
+    };
+    Padding {
+        left: side(live_id!(left)),
+        top: side(live_id!(top)),
+        right: side(live_id!(right)),
+        bottom: side(live_id!(bottom)),
+    }
+}
+
+// A widget as it was last drawn.
+#[derive(Clone, Debug)]
+pub struct DrawnWidget {
+    // The ids from the window down to the widget, e.g. `[body, RowView2, ColumnView2]`. Unnamed
+    // instances are left out.
+    pub path: Vec<LiveId>,
+    // The rect the widget was drawn in, without its margin. `None` if it wasn't drawn, e.g.
+    // because it is hidden, and then its children are left out.
+    pub rect: Option<Rect>,
+    // The margin of the widget's walk.
+    pub margin: Padding,
+    // The padding of the widget's layout. Space that alignment leaves free isn't included.
+    pub padding: Padding,
+}
+
+impl DrawnWidget {
+    // The id of the widget itself.
+    pub fn id(&self) -> LiveId {
+        self.path.last().copied().unwrap_or(LiveId(0))
+    }
+}
+
+// The widgets of `tree` as they were last drawn, parents before their children.
+pub fn drawn_widgets(cx: &mut Cx, tree: &[LayoutNode]) -> Vec<DrawnWidget> {
+    let mut widgets = Vec::new();
+    collect_drawn(cx, tree, &mut Vec::new(), &mut widgets);
+    widgets
+}
+
+fn collect_drawn(cx: &mut Cx, tree: &[LayoutNode], path: &mut Vec<LiveId>, widgets: &mut Vec<DrawnWidget>) {
+    for node in tree {
+        path.push(node.id);
+        let area = node.widget.area();
+        let rect = if area.is_valid(cx) {Some(area.rect(cx))} else {None};
+        let walk_margin = node.widget.walk(cx).margin;
+        let margin = Padding {
+            left: walk_margin.left,
+            top: walk_margin.top,
+            right: walk_margin.right,
+            bottom: walk_margin.bottom,
+        };
+        let inspected = inspect_layout(cx, &node.widget);
+        let padding = inspected.padding.unwrap_or(node.padding);
+        widgets.push(DrawnWidget {path: path.clone(), rect, margin, padding});
+        if rect.is_some() {
+            collect_drawn(cx, &node.children, path, widgets);
+            collect_drawn(cx, &inspected.children, path, widgets);
+        }
+        path.pop();
+    }
+}
+
+// The smallest rect containing `a` and `b`.
+pub fn union(a: Rect, b: Rect) -> Rect {
+    let min = dvec2(a.pos.x.min(b.pos.x), a.pos.y.min(b.pos.y));
+    let max = dvec2(
+        (a.pos.x + a.size.x).max(b.pos.x + b.size.x),
+        (a.pos.y + a.size.y).max(b.pos.y + b.size.y),
+    );
+    Rect {pos: min, size: max - min}
+}
+
+// `rect` grown by `margin` on each side.
+pub fn outset(rect: Rect, margin: &Padding) -> Rect {
+    Rect {
+        pos: dvec2(rect.pos.x - margin.left, rect.pos.y - margin.top),
+        size: dvec2(
+            rect.size.x + margin.left + margin.right,
+            rect.size.y + margin.top + margin.bottom,
+        ),
+    }
+}
+
+// `rect` shrunk by `padding` on each side, down to zero.
+pub fn inset(rect: Rect, padding: &Padding) -> Rect {
+    Rect {
+        pos: dvec2(rect.pos.x + padding.left, rect.pos.y + padding.top),
+        size: dvec2(
+            (rect.size.x - padding.left - padding.right).max(0.0),
+            (rect.size.y - padding.top - padding.bottom).max(0.0),
+        ),
+    }
+}
+
+// The band between `outer` and `inner`, which lies within it, as the top, bottom, left and right
+// rects. Bands of no width are empty.
+pub fn band(outer: Rect, inner: Rect) -> [Rect; 4] {
+    let top = inner.pos.y - outer.pos.y;
+    let bottom = (outer.pos.y + outer.size.y) - (inner.pos.y + inner.size.y);
+    [
+        Rect {pos: outer.pos, size: dvec2(outer.size.x, top)},
+        Rect {pos: dvec2(outer.pos.x, inner.pos.y + inner.size.y), size: dvec2(outer.size.x, bottom)},
+        Rect {pos: dvec2(outer.pos.x, inner.pos.y), size: dvec2(inner.pos.x - outer.pos.x, inner.size.y)},
+        Rect {
+            pos: dvec2(inner.pos.x + inner.size.x, inner.pos.y),
+            size: dvec2((outer.pos.x + outer.size.x) - (inner.pos.x + inner.size.x), inner.size.y),
+        },
+    ]
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct LayoutOverlay {
+    #[walk] walk: Walk,
+
+    #[live] draw_margin: DrawColor,
+    #[live] draw_padding: DrawColor,
+    #[redraw] #[live] draw_outline: DrawColor,
+    #[live] draw_label: DrawText,
+
+    // The widgets shown, empty while the overlay is disabled.
+    #[rust] tree: Vec<LayoutNode>,
+}
+
+impl Widget for LayoutOverlay {
+    fn handle_event(&mut self, _cx: &mut Cx, _event: &Event, _scope: &mut Scope) {}
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, _walk: Walk) -> DrawStep {
+        // Drawn at absolute positions, without taking up any space in the window's layout.
+        for widget in drawn_widgets(cx, &self.tree) {
+            let Some(rect) = widget.rect else {
+                continue;
+            };
+            if rect.size.x <= 0.0 || rect.size.y <= 0.0 {
+                continue;
+            }
+            for margin in band(outset(rect, &widget.margin), rect) {
+                if margin.size.x > 0.0 && margin.size.y > 0.0 {
+                    self.draw_margin.draw_abs(cx, margin);
+                }
+            }
+            for padding in band(rect, inset(rect, &widget.padding)) {
+                if padding.size.x > 0.0 && padding.size.y > 0.0 {
+                    self.draw_padding.draw_abs(cx, padding);
+                }
+            }
+            self.draw_outline.draw_abs(cx, rect);
+            self.draw_label.draw_abs(cx, rect.pos + dvec2(2.0, 1.0), &widget.id().to_string());
+        }
+        DrawStep::done()
+    }
+}
+
+impl LayoutOverlayRef {
+    // Shows the widgets of `tree`, or nothing if it is empty.
+    pub fn set_tree(&self, cx: &mut Cx, tree: Vec<LayoutNode>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.tree = tree;
+            cx.redraw_all();
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.borrow().is_some_and(|inner| !inner.tree.is_empty())
+    }
+}
@@ -0,0 +1,92 @@
+use makepad_widgets::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+// Replacing the items of a `DropDown` at runtime, for lists that come from data rather than from
+// the static `values`/`labels` arrays in the DSL:
+//
+//     let dropdown = ui.drop_down(id!(items_dropdown.dropdown));
+//     dropdown.set_items(cx, vec![DropDownItem::from_label("One"), DropDownItem::from_label("Two")]);
+//     let selected = dropdown.selected_value();
+//
+// The selected item is kept by value when the list changes: if the previously selected value is
+// still in the new list it stays selected, wherever it moved to. The first list set replaces the
+// one from the DSL, whose values can't be read back from the dropdown, so it starts out with its
+// first item selected.
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DropDownItem {
+    pub value: LiveId,
+    pub label: String,
+}
+
+impl DropDownItem {
+    pub fn new(value: LiveId, label: impl Into<String>) -> Self {
+        Self {value, label: label.into()}
+    }
+
+    // An item whose value is derived from its label.
+    pub fn from_label(label: impl Into<String>) -> Self {
+        let label = label.into();
+        Self {value: LiveId::from_str(&label), label}
+    }
+}
+
+thread_local! {
+    // The items set from Rust, by dropdown. The dropdown itself only keeps the labels and values,
+    // and has no getter for the values.
+    static ITEMS: RefCell<HashMap<WidgetUid, Vec<DropDownItem>>> = RefCell::new(HashMap::new());
+}
+
+pub trait DropDownItemsExt {
+    // Replaces the items of the dropdown and redraws it.
+    fn set_items(&self, cx: &mut Cx, items: Vec<DropDownItem>);
+    // The items set with `set_items`, empty if the dropdown still has those of the DSL.
+    fn items(&self) -> Vec<DropDownItem>;
+    // The selected item, if the items were set with `set_items`.
+    fn selected_entry(&self) -> Option<DropDownItem>;
+    // The value of the selected item, if the items were set with `set_items`.
+    fn selected_value(&self) -> Option<LiveId>;
+}
+
+impl DropDownItemsExt for DropDownRef {
+    fn set_items(&self, cx: &mut Cx, items: Vec<DropDownItem>) {
+        if self.borrow().is_none() {
+            return;
+        }
+        let selected = self
+            .selected_value()
+            .and_then(|value| items.iter().position(|item| item.value == value))
+            .unwrap_or(0);
+
+        let mut nodes = vec![LiveNode::from_value(LiveValue::Object)];
+        nodes.push(LiveNode::from_id_value(live_id!(labels), LiveValue::Array));
+        for item in &items {
+            nodes.push(LiveNode::from_id_value(LiveId(0), LiveValue::String(item.label.clone().into())));
+        }
+        nodes.push(LiveNode::from_value(LiveValue::Close));
+        nodes.push(LiveNode::from_id_value(live_id!(values), LiveValue::Array));
+        for item in &items {
+            nodes.push(LiveNode::from_id_value(LiveId(0), LiveValue::Id(item.value)));
+        }
+        nodes.push(LiveNode::from_value(LiveValue::Close));
+        nodes.push(LiveNode::from_value(LiveValue::Close));
+        self.apply_over(cx, &nodes);
+        self.set_selected_item(selected);
+        self.redraw(cx);
+        ITEMS.with(|all| all.borrow_mut().insert(self.widget_uid(), items));
+    }
+
+    fn items(&self) -> Vec<DropDownItem> {
+        ITEMS.with(|all| all.borrow().get(&self.widget_uid()).cloned().unwrap_or_default())
+    }
+
+    fn selected_entry(&self) -> Option<DropDownItem> {
+        let selected = self.selected_item();
+        ITEMS.with(|all| all.borrow().get(&self.widget_uid())?.get(selected).cloned())
+    }
+
+    fn selected_value(&self) -> Option<LiveId> {
+        self.selected_entry().map(|item| item.value)
+    }
+}
@@ -0,0 +1,158 @@
+use makepad_widgets::*;
+
+// An undo/redo history of edits to values in a `DataBindingStore`.
+//
+// Each entry records the fields a batch of actions changed, with their previous and new values.
+// Undoing an entry writes the previous values back, redoing it the new ones; the caller then
+// pushes the store to the widgets with `data_to_widgets`, so every widget bound to a field
+// (and everything derived from it) is restored the same way as when the user edits it.
+//
+// Consecutive edits of the same text field are coalesced into a single entry, so undo reverts a
+// whole typing burst rather than one character at a time. Edits made between `begin_group` and
+// `end_group`, e.g. the repeats of a held button, are likewise merged into one entry.
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub field: Vec<LiveId>,
+    pub old: LiveValue,
+    pub new: LiveValue,
+}
+
+impl Change {
+    pub fn new(field: &[LiveId], old: LiveValue, new: LiveValue) -> Self {
+        Self {field: field.to_vec(), old, new}
+    }
+
+    fn is_text(&self) -> bool {
+        matches!(self.new, LiveValue::String(_) | LiveValue::InlineString(_) | LiveValue::Str(_))
+    }
+}
+
+pub const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+pub struct History {
+    undo: Vec<Vec<Change>>,
+    redo: Vec<Vec<Change>>,
+    // The maximum number of undo entries, older ones are dropped.
+    limit: usize,
+    // Whether a text edit may be merged into the last undo entry. Reset by undo and redo, so that
+    // typing after them starts a new entry.
+    coalesce: bool,
+    // Whether a group is open, see `begin_group`.
+    grouping: bool,
+    // Whether the last undo entry was pushed in the open group.
+    group_entry: bool,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_LIMIT)
+    }
+}
+
+impl History {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            limit: limit.max(1),
+            coalesce: false,
+            grouping: false,
+            group_entry: false,
+        }
+    }
+
+    // Merges the edits pushed until `end_group` into a single entry.
+    pub fn begin_group(&mut self) {
+        self.grouping = true;
+        self.group_entry = false;
+    }
+
+    pub fn end_group(&mut self) {
+        self.grouping = false;
+        self.group_entry = false;
+    }
+
+    // Records an edit made by the user. Clears the redo entries.
+    pub fn push(&mut self, changes: Vec<Change>) {
+        let changes: Vec<Change> = changes.into_iter().filter(|change| change.old != change.new).collect();
+        if changes.is_empty() {
+            return;
+        }
+        self.redo.clear();
+
+        if self.group_entry {
+            if let Some(last) = self.undo.last_mut() {
+                for change in changes {
+                    match last.iter_mut().find(|recorded| recorded.field == change.field) {
+                        Some(recorded) => recorded.new = change.new,
+                        None => last.push(change),
+                    }
+                }
+                last.retain(|change| change.old != change.new);
+                // Changing back to the original values leaves nothing to undo.
+                if last.is_empty() {
+                    self.undo.pop();
+                    self.group_entry = false;
+                }
+                return;
+            }
+        }
+
+        if let [change] = changes.as_slice() {
+            if change.is_text() && self.coalesce {
+                if let Some([last]) = self.undo.last_mut().map(Vec::as_mut_slice) {
+                    if last.field == change.field && last.is_text() {
+                        last.new = change.new.clone();
+                        // Typing back to the original text leaves nothing to undo.
+                        if last.old == last.new {
+                            self.undo.pop();
+                            self.coalesce = false;
+                        }
+                        return;
+                    }
+                }
+            }
+        }
+
+        self.coalesce = changes.len() == 1 && changes[0].is_text();
+        self.group_entry = self.grouping;
+        self.undo.push(changes);
+        if self.undo.len() > self.limit {
+            self.undo.remove(0);
+        }
+    }
+
+    // Takes the last edit off the undo stack. The caller restores the `old` values.
+    pub fn undo(&mut self) -> Option<Vec<Change>> {
+        self.coalesce = false;
+        self.group_entry = false;
+        let changes = self.undo.pop()?;
+        self.redo.push(changes.clone());
+        Some(changes)
+    }
+
+    // Takes the last undone edit off the redo stack. The caller restores the `new` values.
+    pub fn redo(&mut self) -> Option<Vec<Change>> {
+        self.coalesce = false;
+        self.group_entry = false;
+        let changes = self.redo.pop()?;
+        self.undo.push(changes.clone());
+        Some(changes)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.coalesce = false;
+        self.group_entry = false;
+    }
+}
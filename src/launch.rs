@@ -0,0 +1,202 @@
+use makepad_widgets::*;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+// The sample app ships several "versions" of its UI (see the `App` object in `app.rs`). Which one
+// is shown is decided once at startup, either from the `--mode` command-line argument or from the
+// `MAKEPAD_SAMPLE_MODE` environment variable. The command-line argument wins when both are set.
+//
+// The file the app saves its state to on exit (see `persist.rs`) is chosen the same way, with
+// `--state-file` or `MAKEPAD_SAMPLE_STATE_FILE`. `--no-state` disables saving and restoring.
+// Without either, the state is kept in the platform's data directory, see `data_dir`.
+//
+// `--debug-redraws` logs the widgets redrawn by reactive updates (see `reactive.rs`) every frame.
+// `--debug-layout` starts with the layout debug overlay shown (see `debug_layout.rs`).
+//
+// Examples:
+//
+//     cargo run -- --mode layout
+//     MAKEPAD_SAMPLE_MODE=slides cargo run
+//     cargo run -- --state-file /tmp/sample.ron
+
+pub const MODE_ENV_VAR: &str = "MAKEPAD_SAMPLE_MODE";
+pub const STATE_FILE_ENV_VAR: &str = "MAKEPAD_SAMPLE_STATE_FILE";
+// The state file in the data directory is `makepad_widgets_sample/state.ron`.
+pub const APP_DIR_NAME: &str = "makepad_widgets_sample";
+pub const STATE_FILE_NAME: &str = "state.ron";
+
+// The window tree the app instantiates at startup.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AppMode {
+    // A navigation shell switching between all of the demos below at runtime.
+    #[default]
+    Shell,
+    // Basic widgets: buttons, labels, text input and a dropdown.
+    Widgets,
+    // Buttons placed around the screen using row/column layouts.
+    Layout,
+    // A slideshow combining the widgets and layout demos.
+    Slides,
+}
+
+impl AppMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "shell" => Some(Self::Shell),
+            "widgets" | "widget" => Some(Self::Widgets),
+            "layout" => Some(Self::Layout),
+            "slides" | "slide" | "slider" => Some(Self::Slides),
+            _ => None,
+        }
+    }
+}
+
+// Where the app state is saved on exit and restored from on startup.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum StateFile {
+    // The state file in the platform's data directory.
+    #[default]
+    Default,
+    Path(PathBuf),
+    // Nothing is saved or restored.
+    Disabled,
+}
+
+impl StateFile {
+    // The path of the state file, if any. The default one is only known once the app runs, as the
+    // data directory of mobile apps is handed to them by the platform.
+    pub fn resolve(&self, cx: &Cx) -> Option<PathBuf> {
+        match self {
+            Self::Default => data_dir(cx).map(|dir| dir.join(APP_DIR_NAME).join(STATE_FILE_NAME)),
+            Self::Path(path) => Some(path.clone()),
+            Self::Disabled => None,
+        }
+    }
+}
+
+// The directory the platform keeps app data in: the app's `files` directory on Android, and see
+// `data_dir_from_env` elsewhere.
+pub fn data_dir(cx: &Cx) -> Option<PathBuf> {
+    if let OsType::Android(params) = cx.os_type() {
+        // The cache directory the app is given is `<app data>/cache`, next to `<app data>/files`.
+        return Path::new(&params.cache_path).parent().map(|dir| dir.join("files"));
+    }
+    data_dir_from_env(|name| std::env::var(name).ok())
+}
+
+// The data directory as found from environment variables, looked up with `env`:
+//
+// - Windows: `%APPDATA%`,
+// - macOS and iOS: `$HOME/Library/Application Support`, which is inside the app's sandbox on iOS,
+// - Linux and others: `$XDG_DATA_HOME`, or `$HOME/.local/share` if it isn't set.
+//
+// `None` if the variables aren't set, e.g. on the web.
+pub fn data_dir_from_env(env: impl Fn(&str) -> Option<String>) -> Option<PathBuf> {
+    let var = |name: &str| env(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+    if cfg!(target_os = "windows") {
+        var("APPDATA")
+    } else if cfg!(any(target_os = "macos", target_os = "ios")) {
+        var("HOME").map(|home| home.join("Library").join("Application Support"))
+    } else {
+        var("XDG_DATA_HOME").or_else(|| var("HOME").map(|home| home.join(".local").join("share")))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LaunchOptions {
+    pub mode: AppMode,
+    pub state_file: StateFile,
+    // Whether to log the number of reactive redraws per frame.
+    pub debug_redraws: bool,
+    // Whether to show the layout debug overlay from the start.
+    pub debug_layout: bool,
+}
+
+impl Default for LaunchOptions {
+    fn default() -> Self {
+        Self {
+            mode: AppMode::default(),
+            state_file: StateFile::Default,
+            debug_redraws: false,
+            debug_layout: false,
+        }
+    }
+}
+
+static LAUNCH_OPTIONS: OnceLock<LaunchOptions> = OnceLock::new();
+
+impl LaunchOptions {
+    // Parses the options from command-line arguments (without the program name) and environment
+    // variables, looked up with `env`. Unknown arguments are ignored, as the platform layer may
+    // pass its own.
+    pub fn parse<I>(args: I, env: impl Fn(&str) -> Option<String>) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut options = Self::default();
+        if let Some(mode) = env(MODE_ENV_VAR).as_deref().and_then(Self::parse_mode) {
+            options.mode = mode;
+        }
+        if let Some(path) = env(STATE_FILE_ENV_VAR) {
+            options.state_file = Self::parse_state_file(path);
+        }
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // Both `--name value` and `--name=value` are accepted.
+            let (name, value) = match arg.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            match name.as_str() {
+                "--mode" => {
+                    if let Some(mode) = value.or_else(|| args.next()).as_deref().and_then(Self::parse_mode) {
+                        options.mode = mode;
+                    }
+                }
+                "--state-file" => {
+                    if let Some(path) = value.or_else(|| args.next()) {
+                        options.state_file = Self::parse_state_file(path);
+                    }
+                }
+                "--no-state" => options.state_file = StateFile::Disabled,
+                "--debug-redraws" => options.debug_redraws = true,
+                "--debug-layout" => options.debug_layout = true,
+                _ => ()
+            }
+        }
+        options
+    }
+
+    pub fn from_env() -> Self {
+        Self::parse(std::env::args().skip(1), |name| std::env::var(name).ok())
+    }
+
+    // Sets the options used by the app. Has no effect once the app has read them.
+    pub fn init(options: LaunchOptions) {
+        if LAUNCH_OPTIONS.set(options).is_err() {
+            log!("Launch options were already initialized, ignoring");
+        }
+    }
+
+    // The options the app was launched with. Platforms that don't go through `main.rs` (mobile,
+    // wasm) fall back to parsing the environment here.
+    pub fn current() -> &'static LaunchOptions {
+        LAUNCH_OPTIONS.get_or_init(Self::from_env)
+    }
+
+    fn parse_mode(name: &str) -> Option<AppMode> {
+        let mode = AppMode::from_name(name);
+        if mode.is_none() {
+            log!("Unknown mode \"{}\", expected one of: shell, widgets, layout, slides", name);
+        }
+        mode
+    }
+
+    fn parse_state_file(path: String) -> StateFile {
+        if path.is_empty() {
+            StateFile::Disabled
+        } else {
+            StateFile::Path(PathBuf::from(path))
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use makepad_widgets::*;
+
+// An interactive playground for the layout model. The controls on the left change the layout
+// properties of the `target` view on the right while the app runs, with `apply_over`, and show
+// the DSL for the current settings so it can be copied into a `live_design!` block:
+//
+// - `flow_dropdown` picks the `flow`,
+// - `align_x` and `align_y` set the `align` of the target's children,
+// - `spacing` sets the space between the children,
+// - `padding` and `margin` set the padding of the target and its margin within the `stage`,
+//   the same on all four sides.
+//
+// The `snippet` input is read-only: it can be selected and copied like any text input, or copied
+// as a whole with `copy_button`, but only the controls change it.
+live_design!{
+    import makepad_widgets::base::*;
+    import makepad_widgets::theme_desktop_dark::*;
+
+    PlaygroundSlider = <Slider> {
+        width: Fill,
+        height: 30,
+        min: 0.0,
+        max: 50.0,
+        step: 1.0,
+    }
+
+    PlaygroundItem = <Button> {
+        width: 90,
+    }
+
+    LayoutPlayground = {{LayoutPlayground}} {
+        width: Fill,
+        height: Fill,
+        flow: Right,
+        spacing: 20,
+        padding: {left: 20, top: 20, right: 20, bottom: 20},
+
+        controls = <View> {
+            width: 280,
+            height: Fill,
+            flow: Down,
+            spacing: 10,
+
+            flow_label = <Label> {
+                draw_text: {color: #f},
+                text: "flow"
+            }
+            flow_dropdown = <DropDown> {
+                width: Fill,
+                labels: ["Right", "Down", "Overlay", "RightWrap"]
+                values: [Right, Down, Overlay, RightWrap]
+            }
+            align_x = <PlaygroundSlider> {text: "align.x", max: 1.0, step: 0.05}
+            align_y = <PlaygroundSlider> {text: "align.y", max: 1.0, step: 0.05}
+            spacing = <PlaygroundSlider> {text: "spacing"}
+            padding = <PlaygroundSlider> {text: "padding"}
+            margin = <PlaygroundSlider> {text: "margin"}
+
+            snippet = <TextInput> {
+                width: Fill,
+                height: Fit,
+                is_read_only: true,
+                draw_text: {
+                    text_style: {font_size: 10},
+                    wrap: Word,
+                }
+                text: ""
+            }
+            copy_button = <Button> {
+                text: "Copy"
+            }
+        }
+
+        stage = <View> {
+            width: Fill,
+            height: Fill,
+            show_bg: true,
+            draw_bg: {color: #x3A}
+
+            target = <View> {
+                width: Fill,
+                height: Fill,
+                show_bg: true,
+                draw_bg: {color: #x55}
+                flow: Right,
+
+                item1 = <PlaygroundItem> {text: "One"}
+                item2 = <PlaygroundItem> {text: "Two"}
+                item3 = <PlaygroundItem> {text: "Three"}
+            }
+        }
+    }
+}
+
+// The layout settings of the playground.
+#[derive(Clone, Copy, Debug)]
+pub struct PlaygroundLayout {
+    pub flow: Flow,
+    pub align_x: f64,
+    pub align_y: f64,
+    pub spacing: f64,
+    pub padding: f64,
+    pub margin: f64,
+}
+
+impl Default for PlaygroundLayout {
+    fn default() -> Self {
+        Self {
+            flow: Flow::Right,
+            align_x: 0.0,
+            align_y: 0.0,
+            spacing: 0.0,
+            padding: 0.0,
+            margin: 0.0,
+        }
+    }
+}
+
+impl PlaygroundLayout {
+    // The flows in the order of the `flow_dropdown` items.
+    pub const FLOWS: [Flow; 4] = [Flow::Right, Flow::Down, Flow::Overlay, Flow::RightWrap];
+
+    // The DSL for a view with these settings.
+    pub fn snippet(&self) -> String {
+        format!(
+            "<View> {{\n    flow: {:?},\n    align: {{x: {}, y: {}}},\n    spacing: {},\n    padding: {},\n    margin: {},\n}}",
+            self.flow, self.align_x, self.align_y, self.spacing, self.padding, self.margin
+        )
+    }
+}
+
+#[derive(Live, Widget)]
+pub struct LayoutPlayground {
+    #[deref] view: View,
+
+    #[rust] layout: PlaygroundLayout,
+}
+
+impl LiveHook for LayoutPlayground {
+    fn after_new_from_doc(&mut self, cx: &mut Cx) {
+        self.apply_layout(cx);
+    }
+}
+
+impl Widget for LayoutPlayground {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+        self.widget_match_event(cx, event, scope);
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+impl WidgetMatchEvent for LayoutPlayground {
+    fn handle_actions(&mut self, cx: &mut Cx, actions: &Actions, _scope: &mut Scope) {
+        let mut changed = false;
+        if let Some(index) = self.drop_down(id!(flow_dropdown)).changed(actions) {
+            self.layout.flow = PlaygroundLayout::FLOWS.get(index).copied().unwrap_or(Flow::Right);
+            changed = true;
+        }
+        let sliders: [(&[LiveId], &mut f64); 5] = [
+            (id!(align_x), &mut self.layout.align_x),
+            (id!(align_y), &mut self.layout.align_y),
+            (id!(spacing), &mut self.layout.spacing),
+            (id!(padding), &mut self.layout.padding),
+            (id!(margin), &mut self.layout.margin),
+        ];
+        for (path, value) in sliders {
+            if let Some(slided) = self.view.slider(path).slided(actions) {
+                *value = slided;
+                changed = true;
+            }
+        }
+        if changed {
+            self.apply_layout(cx);
+        }
+
+        if self.button(id!(copy_button)).clicked(actions) {
+            cx.copy_to_clipboard(&self.layout.snippet());
+        }
+    }
+}
+
+impl LayoutPlayground {
+    // Applies the settings to the target view and updates the snippet.
+    fn apply_layout(&mut self, cx: &mut Cx) {
+        let layout = self.layout;
+        let target = self.view(id!(target));
+        match layout.flow {
+            Flow::Right => target.apply_over(cx, live!{flow: Right}),
+            Flow::Down => target.apply_over(cx, live!{flow: Down}),
+            Flow::Overlay => target.apply_over(cx, live!{flow: Overlay}),
+            Flow::RightWrap => target.apply_over(cx, live!{flow: RightWrap}),
+        }
+        target.apply_over(cx, live!{
+            align: {x: (layout.align_x), y: (layout.align_y)},
+            spacing: (layout.spacing),
+            padding: {left: (layout.padding), top: (layout.padding), right: (layout.padding), bottom: (layout.padding)},
+            margin: {left: (layout.margin), top: (layout.margin), right: (layout.margin), bottom: (layout.margin)},
+        });
+        self.text_input(id!(snippet)).set_text(&layout.snippet());
+        self.redraw(cx);
+    }
+}
+
+impl LayoutPlaygroundRef {
+    pub fn layout(&self) -> PlaygroundLayout {
+        self.borrow().map(|inner| inner.layout).unwrap_or_default()
+    }
+}
@@ -1,2 +1,28 @@
-use makepad_widgets;
-pub mod app;
\ No newline at end of file
+// Re-exported for the macros of this crate, such as `drop_down_enum!`.
+pub use makepad_widgets;
+pub mod alignment_matrix;
+pub mod app;
+pub mod combo_box;
+pub mod counter;
+pub mod debug_layout;
+pub mod dropdown_items;
+pub mod history;
+pub mod launch;
+pub mod layout_playground;
+pub mod multi_select;
+pub mod password_input;
+pub mod persist;
+pub mod popup_list;
+pub mod reactive;
+pub mod repeat_button;
+pub mod responsive;
+pub mod shortcuts;
+// Test drivers, only built with the `test-harness` feature, which the tests in `tests/` enable.
+#[cfg(feature = "test-harness")]
+pub mod snapshot;
+#[cfg(feature = "test-harness")]
+pub mod test_harness;
+pub mod text_area;
+pub mod transform;
+pub mod typed_dropdown;
+pub mod validation;
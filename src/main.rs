@@ -1,6 +1,9 @@
 // this stub is necessary because some platforms require building
 // as dll (mobile / wasm) and some require to be built as executable
 // unfortunately cargo doesn't facilitate this without a main.rs stub
+use makepad_widgets_sample::launch::LaunchOptions;
+
 fn main(){
+    LaunchOptions::init(LaunchOptions::from_env());
     makepad_widgets_sample::app::app_main()
-}
\ No newline at end of file
+}
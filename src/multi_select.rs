@@ -0,0 +1,307 @@
+use makepad_widgets::*;
+use std::collections::HashSet;
+use crate::persist::{read_ids, write_ids};
+use crate::popup_list::{DrawHover, PopupItem, PopupList, PopupListAction};
+
+// A dropdown where any number of items can be selected. Clicking an item in the popup toggles it,
+// shown with a check mark drawn by the `draw_item` shader, and the popup stays open until the
+// user clicks outside of it or presses Escape. The closed state shows the selected labels joined
+// with commas, or "N selected" once there are more than `max_summary_items` of them.
+//
+// Items are declared with `values` (ids) and `labels` like for a `DropDown`, and the selection is
+// a set of those ids. Every change is reported with a `MultiSelectAction::Changed` action. A
+// value without a label, or a label without a value, is logged and left out.
+//
+// When bound to a `DataBindingStore`, the selection is stored as an array of the selected ids, in
+// the order of the items, see `write_ids` and `read_ids` in `persist.rs`.
+live_design!{
+    import makepad_widgets::base::*;
+    import makepad_widgets::theme_desktop_dark::*;
+    import makepad_draw::shader::std::*;
+    import crate::popup_list::*;
+
+    MultiSelectDropDown = {{MultiSelectDropDown}} {
+        width: Fit,
+        height: Fit,
+        padding: {top: 8.0, right: 16.0, bottom: 8.0, left: 8.0}
+        max_summary_items: 2,
+        empty_text: "None",
+
+        draw_bg: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.rect(0, 0, self.rect_size.x, self.rect_size.y);
+                sdf.fill(mix(#xFFFFFF00, #xFFFFFF10, self.hover));
+                // triangle
+                let c = vec2(self.rect_size.x - 10.0, self.rect_size.y * 0.5)
+                let sz = 2.5;
+                sdf.move_to(c.x - sz, c.y - sz);
+                sdf.line_to(c.x + sz, c.y - sz);
+                sdf.line_to(c.x, c.y + sz * 0.75);
+                sdf.close_path();
+                sdf.fill(mix(#FFFA, #FFFF, max(self.hover, self.selected)));
+                return sdf.result
+            }
+        }
+
+        draw_text: {
+            color: #xFFFFFFCC
+        }
+
+        popup: <PopupList> {
+            item_padding: {top: 8.0, right: 16.0, bottom: 8.0, left: 24.0}
+            draw_item: {
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    sdf.rect(0, 0, self.rect_size.x, self.rect_size.y);
+                    sdf.fill(mix(#x48, #x5A, self.hover));
+                    // check mark
+                    let c = vec2(12.0, self.rect_size.y * 0.5);
+                    let sz = 3.5;
+                    sdf.move_to(c.x - sz, c.y);
+                    sdf.line_to(c.x - sz * 0.25, c.y + sz * 0.75);
+                    sdf.line_to(c.x + sz, c.y - sz);
+                    sdf.stroke(mix(#xFFFFFF00, #xFFFFFFFF, self.selected), 1.25);
+                    return sdf.result
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum MultiSelectAction {
+    // The selection was changed by the user, carries the new selection.
+    Changed(HashSet<LiveId>),
+    None,
+}
+
+#[derive(Live, Widget)]
+pub struct MultiSelectDropDown {
+    // The closed state: a summary of the selection and a triangle.
+    #[redraw] #[live] draw_bg: DrawHover,
+    #[live] draw_text: DrawText,
+    #[walk] walk: Walk,
+    #[layout] layout: Layout,
+    #[live] max_summary_items: usize,
+    #[live] empty_text: String,
+
+    #[live] popup: PopupList,
+
+    #[live] labels: Vec<String>,
+    #[live] values: Vec<LiveValue>,
+
+    #[rust] selected: HashSet<LiveId>,
+    #[rust] is_open: bool,
+    #[rust] hovered: Option<usize>,
+}
+
+impl LiveHook for MultiSelectDropDown {
+    fn after_apply(&mut self, _cx: &mut Cx, _from: ApplyFrom, _index: usize, _nodes: &[LiveNode]) {
+        if self.labels.len() != self.values.len() {
+            log!(
+                "MultiSelectDropDown has {} labels but {} values, only the first {} items are shown",
+                self.labels.len(),
+                self.values.len(),
+                self.item_count()
+            );
+        }
+    }
+}
+
+impl Widget for MultiSelectDropDown {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if self.is_open {
+            self.handle_popup_event(cx, event, scope);
+        }
+
+        match event.hits(cx, self.draw_bg.area()) {
+            Hit::FingerHoverIn(_) => {
+                cx.set_cursor(MouseCursor::Hand);
+                self.draw_bg.hover = 1.0;
+                self.draw_bg.redraw(cx);
+            }
+            Hit::FingerHoverOut(_) => {
+                self.draw_bg.hover = 0.0;
+                self.draw_bg.redraw(cx);
+            }
+            Hit::FingerDown(_) => {
+                self.is_open = !self.is_open;
+                self.hovered = None;
+                self.popup.reset();
+                self.redraw(cx);
+            }
+            _ => ()
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.draw_bg.selected = if self.is_open {1.0} else {0.0};
+        self.draw_bg.begin(cx, walk, self.layout);
+        let summary = self.summary();
+        self.draw_text.draw_walk(cx, Walk::fit(), Align::default(), &summary);
+        self.draw_bg.end(cx);
+
+        if self.is_open {
+            self.draw_popup(cx);
+        }
+        DrawStep::done()
+    }
+
+    fn widget_to_data(&self, _cx: &mut Cx, actions: &Actions, nodes: &mut LiveNodeVec, path: &[LiveId]) -> bool {
+        match actions.find_widget_action_cast(self.widget_uid()) {
+            MultiSelectAction::Changed(_) => {
+                write_ids(nodes, path, &self.selected_ids());
+                true
+            }
+            _ => false
+        }
+    }
+
+    fn data_to_widget(&mut self, cx: &mut Cx, nodes: &[LiveNode], path: &[LiveId]) {
+        if let Some(ids) = read_ids(nodes, path) {
+            let selected: HashSet<LiveId> = ids.into_iter().collect();
+            if selected != self.selected {
+                self.set_selected(&selected);
+                self.redraw(cx);
+            }
+        }
+    }
+}
+
+impl MultiSelectDropDown {
+    // The number of items, those with both a label and a value.
+    fn item_count(&self) -> usize {
+        self.labels.len().min(self.values.len())
+    }
+
+    fn item_id(&self, index: usize) -> Option<LiveId> {
+        match self.values.get(index) {
+            Some(LiveValue::Id(id)) => Some(*id),
+            _ => None,
+        }
+    }
+
+    fn is_selected(&self, index: usize) -> bool {
+        self.item_id(index).is_some_and(|id| self.selected.contains(&id))
+    }
+
+    // The selected labels joined with commas, or "N selected" if there are too many of them.
+    fn summary(&self) -> String {
+        let labels: Vec<&str> = (0..self.item_count())
+            .filter(|&index| self.is_selected(index))
+            .map(|index| self.labels[index].as_str())
+            .collect();
+        if labels.is_empty() {
+            self.empty_text.clone()
+        } else if labels.len() > self.max_summary_items {
+            format!("{} selected", labels.len())
+        } else {
+            labels.join(", ")
+        }
+    }
+
+    // The selected ids, in the order of the items.
+    fn selected_ids(&self) -> Vec<LiveId> {
+        (0..self.item_count())
+            .filter(|&index| self.is_selected(index))
+            .filter_map(|index| self.item_id(index))
+            .collect()
+    }
+
+    // Ids that aren't values of the dropdown are ignored.
+    fn set_selected(&mut self, selected: &HashSet<LiveId>) {
+        self.selected = (0..self.item_count())
+            .filter_map(|index| self.item_id(index))
+            .filter(|id| selected.contains(id))
+            .collect();
+    }
+
+    fn changed(&mut self, cx: &mut Cx, path: &HeapLiveIdPath) {
+        let selected = self.selected.clone();
+        cx.widget_action(self.widget_uid(), path, MultiSelectAction::Changed(selected));
+        self.redraw(cx);
+    }
+
+    fn toggle(&mut self, cx: &mut Cx, scope: &mut Scope, index: usize) {
+        if let Some(id) = self.item_id(index) {
+            if !self.selected.remove(&id) {
+                self.selected.insert(id);
+            }
+            self.changed(cx, &scope.path);
+        }
+    }
+
+    fn close(&mut self, cx: &mut Cx) {
+        self.is_open = false;
+        self.popup.redraw(cx);
+        self.redraw(cx);
+    }
+
+    fn handle_popup_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if let Event::KeyDown(ke) = event {
+            if ke.key_code == KeyCode::Escape {
+                self.close(cx);
+            }
+            return;
+        }
+
+        match self.popup.handle_event(cx, event, self.draw_bg.area()) {
+            PopupListAction::HoverIn(index) => {
+                self.hovered = Some(index);
+                self.redraw(cx);
+            }
+            PopupListAction::HoverOut(index) if self.hovered == Some(index) => {
+                self.hovered = None;
+                self.redraw(cx);
+            }
+            PopupListAction::Clicked(index) => self.toggle(cx, scope, index),
+            // Clicking anywhere outside of the popup and the closed state closes the popup.
+            PopupListAction::ClickedOutside => self.close(cx),
+            _ => ()
+        }
+    }
+
+    fn draw_popup(&mut self, cx: &mut Cx2d) {
+        self.popup.begin(cx, self.draw_bg.area().rect(cx));
+        let labels = &self.labels;
+        let items: Vec<PopupItem> = (0..self.item_count())
+            .map(|index| PopupItem {
+                label: &labels[index],
+                highlighted: self.hovered == Some(index),
+                selected: self.is_selected(index),
+            })
+            .collect();
+        self.popup.draw_items(cx, &items);
+        self.popup.end(cx);
+    }
+}
+
+impl MultiSelectDropDownRef {
+    pub fn selected(&self) -> HashSet<LiveId> {
+        if let Some(inner) = self.borrow() {
+            inner.selected.clone()
+        } else {
+            HashSet::new()
+        }
+    }
+
+    // Replaces the selection and redraws. Ids that are not values of the dropdown are ignored.
+    // The change is reported with `MultiSelectAction::Changed` like one made by the user, so that
+    // a bound store is updated too.
+    pub fn set_selected(&self, cx: &mut Cx, selected: &HashSet<LiveId>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_selected(selected);
+            inner.changed(cx, &HeapLiveIdPath::default());
+        }
+    }
+
+    // The new selection, if it was changed by one of the given actions.
+    pub fn changed(&self, actions: &Actions) -> Option<HashSet<LiveId>> {
+        if let MultiSelectAction::Changed(selected) = actions.find_widget_action_cast(self.widget_uid()) {
+            Some(selected)
+        } else {
+            None
+        }
+    }
+}
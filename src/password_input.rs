@@ -0,0 +1,136 @@
+use makepad_widgets::*;
+
+// A `TextInput` in password mode, with an eye button to show the text instead.
+//
+// The inner `input` draws one bullet per character, but `text()` on either the `PasswordInput` or
+// its `input` returns the real text. The eye button (`reveal`) toggles between bullets and plain
+// text, and can be left out with `show_toggle: false`:
+//
+//     password = <PasswordInput> {
+//         show_toggle: true,
+//         input = { empty_message: "Password" }
+//     }
+live_design!{
+    import makepad_widgets::base::*;
+    import makepad_widgets::theme_desktop_dark::*;
+    import makepad_draw::shader::std::*;
+
+    PasswordInput = {{PasswordInput}} {
+        width: Fit,
+        height: Fit,
+        flow: Right,
+        spacing: 4,
+        align: {y: 0.5},
+        show_toggle: true,
+
+        input = <TextInput> {
+            is_password: true,
+        }
+
+        // Wrapped in a view so that it can be hidden.
+        toggle = <View> {
+            width: Fit,
+            height: Fit,
+            reveal = <Button> {
+                width: 28,
+                height: 28,
+                text: "",
+                draw_bg: {
+                    // 1.0 while the text is shown, which removes the slash across the eye.
+                    instance revealed: 0.0
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        let c = self.rect_size * 0.5;
+                        let color = mix(#xFFFFFF88, #xFFFFFFFF, self.hover);
+                        // The outline of the eye: two arcs meeting at the corners.
+                        sdf.circle(c.x, c.y + 6.0, 9.0);
+                        sdf.circle(c.x, c.y - 6.0, 9.0);
+                        sdf.intersect();
+                        sdf.stroke(color, 1.2);
+                        // The pupil.
+                        sdf.circle(c.x, c.y, 2.5);
+                        sdf.fill(color);
+                        // The slash, while the text is hidden.
+                        sdf.move_to(c.x - 8.0, c.y + 8.0);
+                        sdf.line_to(c.x + 8.0, c.y - 8.0);
+                        sdf.stroke(mix(color, #xFFFFFF00, self.revealed), 1.2);
+                        return sdf.result
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Live, Widget)]
+pub struct PasswordInput {
+    #[deref] view: View,
+
+    // Whether the eye button is shown.
+    #[live] show_toggle: bool,
+
+    #[rust] revealed: bool,
+}
+
+impl LiveHook for PasswordInput {
+    fn after_new_from_doc(&mut self, cx: &mut Cx) {
+        let show_toggle = self.show_toggle;
+        self.view(id!(toggle)).set_visible(show_toggle);
+        self.set_revealed(cx, false);
+    }
+}
+
+impl Widget for PasswordInput {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+        self.widget_match_event(cx, event, scope);
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+
+    fn text(&self) -> String {
+        self.text_input(id!(input)).text()
+    }
+
+    fn set_text(&mut self, v: &str) {
+        self.text_input(id!(input)).set_text(v);
+    }
+}
+
+impl WidgetMatchEvent for PasswordInput {
+    fn handle_actions(&mut self, cx: &mut Cx, actions: &Actions, _scope: &mut Scope) {
+        if self.button(id!(reveal)).clicked(actions) {
+            let revealed = !self.revealed;
+            self.set_revealed(cx, revealed);
+        }
+    }
+}
+
+impl PasswordInput {
+    fn set_revealed(&mut self, cx: &mut Cx, revealed: bool) {
+        self.revealed = revealed;
+        self.text_input(id!(input)).apply_over(cx, live!{
+            is_password: (!revealed)
+        });
+        let revealed = if revealed {1.0} else {0.0};
+        self.button(id!(reveal)).apply_over(cx, live!{
+            draw_bg: {revealed: (revealed)}
+        });
+        self.redraw(cx);
+    }
+}
+
+impl PasswordInputRef {
+    // Whether the text is shown instead of bullets.
+    pub fn is_revealed(&self) -> bool {
+        self.borrow().is_some_and(|inner| inner.revealed)
+    }
+
+    pub fn set_revealed(&self, cx: &mut Cx, revealed: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_revealed(cx, revealed);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use makepad_widgets::*;
+use makepad_widgets::makepad_micro_serde::*;
+use std::path::Path;
+
+// Saving and restoring the app's `DataBindingStore` to a RON file, so that the values bound in
+// `App::data_bind` come back the next time the app is started.
+//
+// Only the top-level fields of the store are saved, and only if they hold one of the value types
+// below, or an array of ids such as the selection of a `MultiSelectDropDown`. Everything else is
+// skipped. Ids are saved by name, or as `#` and their hash in hex if the name isn't known, e.g.
+// for ids made from a string at runtime.
+//
+// The file is written next to its final path first and then renamed, so that a crash while saving
+// leaves the previous state intact.
+
+#[derive(SerRon, DeRon)]
+enum StoredValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Id(String),
+    String(String),
+    Ids(Vec<String>),
+}
+
+#[derive(SerRon, DeRon)]
+struct StoredField {
+    id: String,
+    value: StoredValue,
+}
+
+#[derive(SerRon, DeRon, Default)]
+struct StoredState {
+    fields: Vec<StoredField>,
+}
+
+impl StoredValue {
+    fn from_live_value(value: &LiveValue) -> Option<Self> {
+        Some(match value {
+            LiveValue::Bool(v) => Self::Bool(*v),
+            LiveValue::Int64(v) => Self::Int(*v),
+            LiveValue::Float64(v) => Self::Float(*v),
+            LiveValue::Id(v) => Self::Id(id_to_string(*v)),
+            LiveValue::Str(v) => Self::String(v.to_string()),
+            LiveValue::String(v) => Self::String(v.to_string()),
+            LiveValue::InlineString(v) => Self::String(v.as_str().to_string()),
+            _ => return None,
+        })
+    }
+
+    fn write_to(self, nodes: &mut LiveNodeVec, path: &[LiveId]) {
+        let value = match self {
+            Self::Bool(v) => LiveValue::Bool(v),
+            Self::Int(v) => LiveValue::Int64(v),
+            Self::Float(v) => LiveValue::Float64(v),
+            Self::Id(v) => LiveValue::Id(id_from_string(&v)),
+            Self::String(v) => LiveValue::String(v.into()),
+            Self::Ids(ids) => {
+                let ids: Vec<LiveId> = ids.iter().map(|id| id_from_string(id)).collect();
+                write_ids(nodes, path, &ids);
+                return;
+            }
+        };
+        nodes.write_field_value(path, value);
+    }
+}
+
+pub fn save_store(store: &DataBindingStore, path: &Path) -> std::io::Result<()> {
+    let mut state = StoredState::default();
+    let mut child = store.nodes.first_child(0);
+    while let Some(index) = child {
+        let node = &store.nodes[index];
+        let value = if node.value.is_array() {
+            read_ids(&store.nodes, &[node.id]).map(|ids| StoredValue::Ids(ids.into_iter().map(id_to_string).collect()))
+        } else {
+            StoredValue::from_live_value(&node.value)
+        };
+        if let Some(value) = value {
+            state.fields.push(StoredField {id: id_to_string(node.id), value});
+        }
+        child = store.nodes.next_child(index);
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let file_name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    let temp_path = path.with_file_name(format!("{}.tmp", file_name));
+    std::fs::write(&temp_path, state.serialize_ron())?;
+    std::fs::rename(&temp_path, path)
+}
+
+// Writes the fields saved by `save_store` into `store`. A missing file leaves the store untouched.
+pub fn load_store(store: &mut DataBindingStore, path: &Path) -> std::io::Result<()> {
+    let data = match std::fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    let state = StoredState::deserialize_ron(&data).map_err(|err| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{:?}", err))
+    })?;
+    for field in state.fields {
+        field.value.write_to(&mut store.nodes, &[id_from_string(&field.id)]);
+    }
+    Ok(())
+}
+
+// The name of `id`, or `#` and its hash in hex if the name isn't known.
+fn id_to_string(id: LiveId) -> String {
+    id.as_string(|name| match name {
+        Some(name) => name.to_string(),
+        None => format!("#{:016x}", id.0),
+    })
+}
+
+fn id_from_string(s: &str) -> LiveId {
+    match s.strip_prefix('#').and_then(|hex| u64::from_str_radix(hex, 16).ok()) {
+        Some(hash) => LiveId(hash),
+        None => LiveId::from_str_with_lut(s).unwrap_or_else(|_| LiveId::from_str(s)),
+    }
+}
+
+// Writes `ids` as an array at `path`, replacing what was there.
+pub fn write_ids(nodes: &mut LiveNodeVec, path: &[LiveId], ids: &[LiveId]) {
+    let mut array = vec![LiveNode::from_value(LiveValue::Array)];
+    for id in ids {
+        array.push(LiveNode::from_id_value(LiveId(0), LiveValue::Id(*id)));
+    }
+    array.push(LiveNode::from_value(LiveValue::Close));
+    nodes.write_field_nodes(path, &array);
+}
+
+// The ids in the array at `path`, if there is one.
+pub fn read_ids(nodes: &[LiveNode], path: &[LiveId]) -> Option<Vec<LiveId>> {
+    let index = nodes.child_by_field_path(0, path)?;
+    if !nodes[index].value.is_array() {
+        return None;
+    }
+    let mut ids = Vec::new();
+    let mut child = nodes.first_child(index);
+    while let Some(child_index) = child {
+        if let LiveValue::Id(id) = nodes[child_index].value {
+            ids.push(id);
+        }
+        child = nodes.next_child(child_index);
+    }
+    Some(ids)
+}
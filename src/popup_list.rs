@@ -0,0 +1,205 @@
+use makepad_widgets::*;
+
+// The popup list of `ComboBox` and `MultiSelectDropDown`: a column of items drawn in an overlay
+// below the widget it belongs to, its anchor. At most `max_visible_items` items are shown at a
+// time. The list scrolls with the mouse wheel, and `scroll_to` brings an item into view, e.g. the
+// one highlighted with the keyboard.
+//
+// The widget draws the list between `begin` and `end`, and may draw content of its own above the
+// items, such as the filter input of the `ComboBox`:
+//
+//     self.popup.begin(cx, self.draw_bg.area().rect(cx));
+//     let _ = self.filter_input.draw_walk(cx, scope, walk);
+//     self.popup.draw_items(cx, &items);
+//     self.popup.end(cx);
+//
+// The `draw_item` shader gets `hover` set for the highlighted item and `selected` for selected
+// ones.
+live_design!{
+    import makepad_widgets::base::*;
+    import makepad_widgets::theme_desktop_dark::*;
+    import makepad_draw::shader::std::*;
+
+    PopupList = {{PopupList}} {
+        width: 200.0,
+        max_visible_items: 8,
+        padding: {top: 4.0, right: 4.0, bottom: 4.0, left: 4.0}
+        item_padding: {top: 8.0, right: 16.0, bottom: 8.0, left: 16.0}
+
+        draw_bg: {
+            color: #x48
+        }
+
+        draw_item: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.rect(0, 0, self.rect_size.x, self.rect_size.y);
+                sdf.fill(mix(mix(#x48, #x6, self.selected), #x5A, self.hover));
+                return sdf.result
+            }
+        }
+
+        draw_item_text: {
+            color: #xFFFFFFCC
+        }
+    }
+}
+
+// A quad with `hover` and `selected` instance variables for its shader, used for the items of
+// the list and for the widgets that open it.
+#[derive(Live, LiveHook, LiveRegister)]
+#[repr(C)]
+pub struct DrawHover {
+    #[deref] draw_super: DrawQuad,
+    #[live] pub hover: f32,
+    #[live] pub selected: f32,
+}
+
+// An item as drawn in the list.
+pub struct PopupItem<'a> {
+    pub label: &'a str,
+    pub highlighted: bool,
+    pub selected: bool,
+}
+
+// What happened to the list in response to an event, see `PopupList::handle_event`. Items are
+// given by their index in the list passed to `draw_items`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PopupListAction {
+    HoverIn(usize),
+    HoverOut(usize),
+    Clicked(usize),
+    // A finger went down outside of both the list and its anchor.
+    ClickedOutside,
+    None,
+}
+
+#[derive(Live, LiveHook)]
+pub struct PopupList {
+    #[live] draw_bg: DrawColor,
+    #[live] draw_item: DrawHover,
+    #[live] draw_item_text: DrawText,
+    #[live] width: f64,
+    #[live] max_visible_items: usize,
+    #[live] padding: Padding,
+    #[live] item_padding: Padding,
+
+    #[rust(DrawList2d::new(cx))] draw_list: DrawList2d,
+    // The index of the first item shown.
+    #[rust] scroll: usize,
+    // Mouse wheel movement, in pixels, not yet turned into whole items.
+    #[rust] scroll_remainder: f64,
+    // The number of items, as last drawn.
+    #[rust] item_count: usize,
+    #[rust] area: Area,
+    // The areas of the items shown, the first one being that of the item at `scroll`.
+    #[rust] item_areas: Vec<Area>,
+}
+
+impl PopupList {
+    // Scrolls back to the first item, for when the popup opens.
+    pub fn reset(&mut self) {
+        self.scroll = 0;
+        self.scroll_remainder = 0.0;
+    }
+
+    // Scrolls as little as needed for the item at `index` to be shown.
+    pub fn scroll_to(&mut self, index: usize) {
+        let visible = self.max_visible_items.max(1);
+        if index < self.scroll {
+            self.scroll = index;
+        } else if index >= self.scroll + visible {
+            self.scroll = index + 1 - visible;
+        }
+    }
+
+    pub fn redraw(&self, cx: &mut Cx) {
+        self.draw_list.redraw(cx);
+    }
+
+    pub fn handle_event(&mut self, cx: &mut Cx, event: &Event, anchor: Area) -> PopupListAction {
+        if let Event::Scroll(se) = event {
+            if self.area.rect(cx).contains(se.abs) {
+                self.scroll_by(cx, se.scroll.y);
+            }
+            return PopupListAction::None;
+        }
+
+        for row in 0..self.item_areas.len() {
+            match event.hits(cx, self.item_areas[row]) {
+                Hit::FingerHoverIn(_) => return PopupListAction::HoverIn(self.scroll + row),
+                Hit::FingerHoverOut(_) => return PopupListAction::HoverOut(self.scroll + row),
+                Hit::FingerUp(fe) if fe.is_over => return PopupListAction::Clicked(self.scroll + row),
+                _ => ()
+            }
+        }
+
+        if let Event::FingerDown(fe) = event {
+            if !self.area.rect(cx).contains(fe.abs) && !anchor.rect(cx).contains(fe.abs) {
+                return PopupListAction::ClickedOutside;
+            }
+        }
+        PopupListAction::None
+    }
+
+    fn scroll_by(&mut self, cx: &mut Cx, pixels: f64) {
+        let row_height = self.item_areas
+            .first()
+            .map(|area| area.rect(cx).size.y)
+            .filter(|height| *height > 0.0)
+            .unwrap_or(1.0);
+        self.scroll_remainder += pixels;
+        let rows = (self.scroll_remainder / row_height).trunc();
+        self.scroll_remainder -= rows * row_height;
+
+        let max_scroll = self.item_count.saturating_sub(self.max_visible_items);
+        let scroll = (self.scroll as f64 + rows).clamp(0.0, max_scroll as f64) as usize;
+        if scroll != self.scroll {
+            self.scroll = scroll;
+            self.redraw(cx);
+        }
+    }
+
+    // Begins the popup below `anchor`, the rect of the widget it belongs to.
+    pub fn begin(&mut self, cx: &mut Cx2d, anchor: Rect) {
+        self.draw_list.begin_overlay_reuse(cx);
+        cx.begin_pass_sized_turtle(Layout::flow_down());
+
+        let walk = Walk {
+            abs_pos: Some(dvec2(anchor.pos.x, anchor.pos.y + anchor.size.y)),
+            margin: Margin::default(),
+            width: Size::Fixed(self.width),
+            height: Size::Fit,
+        };
+        self.draw_bg.begin(cx, walk, Layout {
+            flow: Flow::Down,
+            padding: self.padding,
+            ..Layout::default()
+        });
+    }
+
+    // Draws the items that are scrolled into view.
+    pub fn draw_items(&mut self, cx: &mut Cx2d, items: &[PopupItem]) {
+        self.item_count = items.len();
+        self.scroll = self.scroll.min(items.len().saturating_sub(self.max_visible_items));
+        self.item_areas.clear();
+        for item in items.iter().skip(self.scroll).take(self.max_visible_items) {
+            self.draw_item.hover = if item.highlighted {1.0} else {0.0};
+            self.draw_item.selected = if item.selected {1.0} else {0.0};
+            self.draw_item.begin(cx, Walk::fill_fit(), Layout {
+                padding: self.item_padding,
+                ..Layout::default()
+            });
+            self.draw_item_text.draw_walk(cx, Walk::fit(), Align::default(), item.label);
+            self.draw_item.end(cx);
+            self.item_areas.push(self.draw_item.area());
+        }
+    }
+
+    pub fn end(&mut self, cx: &mut Cx2d) {
+        self.draw_bg.end(cx);
+        self.area = self.draw_bg.area();
+        cx.end_pass_sized_turtle();
+        self.draw_list.end(cx);
+    }
+}
@@ -0,0 +1,129 @@
+use makepad_widgets::*;
+
+// Event-driven UI updates. Instead of recomputing every derived widget on every batch of actions,
+// updates subscribe to the actions that affect them, e.g. a `TextInputAction::Change` of one
+// input, and declare the widgets that depend on them. When a batch of actions arrives, only the
+// subscriptions whose trigger matches run, and only their dependents are redrawn.
+//
+// Subscriptions run against some state `S` (usually the `App`), which is passed to the trigger
+// and the update:
+//
+//     struct Form {
+//         ui: WidgetRef,
+//     }
+//
+//     reactor.subscribe(
+//         "greeting",
+//         |form: &Form, actions| form.ui.text_input(id!(name)).changed(actions).is_some(),
+//         |form: &mut Form, _cx: &mut Cx| {
+//             let name = form.ui.text_input(id!(name)).text();
+//             form.ui.label(id!(greeting)).set_text(&format!("Hello, {}", name));
+//         },
+//         &[id!(greeting)],
+//     );
+//
+// The number of dependents redrawn is counted per frame, leaving out those the `ui` passed to
+// `dispatch` doesn't have. With `set_debug(true)`, every frame with reactive redraws is logged.
+
+pub type Trigger<S> = Box<dyn Fn(&S, &Actions) -> bool>;
+pub type Update<S> = Box<dyn FnMut(&mut S, &mut Cx)>;
+
+struct Subscription<S> {
+    name: &'static str,
+    trigger: Trigger<S>,
+    update: Update<S>,
+    dependents: Vec<Vec<LiveId>>,
+}
+
+pub struct Reactor<S> {
+    subscriptions: Vec<Subscription<S>>,
+    debug: bool,
+    redraws_this_frame: usize,
+    redraws_last_frame: usize,
+    total_redraws: usize,
+}
+
+impl<S> Default for Reactor<S> {
+    fn default() -> Self {
+        Self {
+            subscriptions: Vec::new(),
+            debug: false,
+            redraws_this_frame: 0,
+            redraws_last_frame: 0,
+            total_redraws: 0,
+        }
+    }
+}
+
+impl<S> Reactor<S> {
+    // Runs `update` whenever `trigger` matches a batch of actions, then redraws the widgets at the
+    // `dependents` paths.
+    pub fn subscribe(
+        &mut self,
+        name: &'static str,
+        trigger: impl Fn(&S, &Actions) -> bool + 'static,
+        update: impl FnMut(&mut S, &mut Cx) + 'static,
+        dependents: &[&[LiveId]],
+    ) {
+        self.subscriptions.push(Subscription {
+            name,
+            trigger: Box::new(trigger),
+            update: Box::new(update),
+            dependents: dependents.iter().map(|path| path.to_vec()).collect(),
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.subscriptions.clear();
+    }
+
+    pub fn set_debug(&mut self, debug: bool) {
+        self.debug = debug;
+    }
+
+    // Runs the subscriptions triggered by `actions`, and redraws their dependents in `ui`.
+    pub fn dispatch(&mut self, state: &mut S, cx: &mut Cx, ui: &WidgetRef, actions: &Actions) {
+        for subscription in &mut self.subscriptions {
+            if !(subscription.trigger)(state, actions) {
+                continue;
+            }
+            (subscription.update)(state, cx);
+            let mut redrawn = 0;
+            for path in &subscription.dependents {
+                let widget = ui.widget(path);
+                if !widget.is_empty() {
+                    widget.redraw(cx);
+                    redrawn += 1;
+                }
+            }
+            self.redraws_this_frame += redrawn;
+            if self.debug {
+                log!("reactive: {} redrew {} widget(s)", subscription.name, redrawn);
+            }
+        }
+    }
+
+    // Closes the redraw count of the current frame. Call on every `Event::Draw`.
+    pub fn end_frame(&mut self) {
+        if self.debug && self.redraws_this_frame > 0 {
+            log!("reactive: {} redraw(s) this frame", self.redraws_this_frame);
+        }
+        self.total_redraws += self.redraws_this_frame;
+        self.redraws_last_frame = self.redraws_this_frame;
+        self.redraws_this_frame = 0;
+    }
+
+    // Dependents redrawn since the last `end_frame`.
+    pub fn redraws_this_frame(&self) -> usize {
+        self.redraws_this_frame
+    }
+
+    pub fn redraws_last_frame(&self) -> usize {
+        self.redraws_last_frame
+    }
+
+    // Dependents redrawn since the reactor was created, including the current frame.
+    pub fn total_redraws(&self) -> usize {
+        self.total_redraws + self.redraws_this_frame
+    }
+}
@@ -0,0 +1,136 @@
+use makepad_widgets::*;
+
+// A button that keeps clicking while it is held down, like the arrows of a spinner control.
+//
+// Pressing the inner `button` clicks once right away. If it is still held after `initial_delay`
+// seconds it clicks again, and then every `interval` seconds, with the interval multiplied by
+// `acceleration` after each repeat until it reaches `min_interval`. Releasing the button, over it
+// or not, stops the repeats, as does the app losing the focus.
+//
+// The clicks are reported as `ButtonAction::Clicked` actions of the `RepeatButton` itself, so
+// `ButtonRef::clicked` works on it as on a plain `Button`:
+//
+//     plus = <RepeatButton> {
+//         initial_delay: 0.5,
+//         button = { text: "+" }
+//     }
+//
+//     if self.button(id!(plus)).clicked(actions) { ... }
+//
+// The repeats of one press are bracketed by `RepeatButtonAction::Started` and `Stopped`, e.g. to
+// undo them as a single edit.
+//
+// With `repeat: false` it behaves like a plain button, clicking once on release.
+live_design!{
+    import makepad_widgets::base::*;
+    import makepad_widgets::theme_desktop_dark::*;
+
+    RepeatButton = {{RepeatButton}} {
+        width: Fit,
+        height: Fit,
+        repeat: true,
+        initial_delay: 0.4,
+        interval: 0.12,
+        acceleration: 0.85,
+        min_interval: 0.02,
+
+        button = <Button> {}
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum RepeatButtonAction {
+    // The button was pressed and clicked for the first time.
+    Started,
+    // The button was released, no more clicks follow.
+    Stopped,
+    None,
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct RepeatButton {
+    #[deref] view: View,
+
+    #[live] repeat: bool,
+    // Seconds between pressing the button and the first repeat.
+    #[live] initial_delay: f64,
+    // Seconds between the first repeats.
+    #[live] interval: f64,
+    // Factor applied to the interval after every repeat.
+    #[live] acceleration: f64,
+    #[live] min_interval: f64,
+
+    #[rust] timer: Timer,
+    #[rust] current_interval: f64,
+}
+
+impl Widget for RepeatButton {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if self.timer.is_event(event).is_some() {
+            self.click(cx, scope);
+            self.current_interval = (self.current_interval * self.acceleration).max(self.min_interval);
+            self.timer = cx.start_timeout(self.current_interval);
+        }
+        if let Event::AppLostFocus = event {
+            self.stop(cx, scope);
+        }
+        self.view.handle_event(cx, event, scope);
+        self.widget_match_event(cx, event, scope);
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+
+    fn text(&self) -> String {
+        self.button(id!(button)).text()
+    }
+
+    fn set_text(&mut self, v: &str) {
+        self.button(id!(button)).set_text(v);
+    }
+}
+
+impl WidgetMatchEvent for RepeatButton {
+    fn handle_actions(&mut self, cx: &mut Cx, actions: &Actions, scope: &mut Scope) {
+        let button = self.button(id!(button));
+        if !self.repeat {
+            if button.clicked(actions) {
+                self.click(cx, scope);
+            }
+            return;
+        }
+        if button.pressed(actions) {
+            cx.widget_action(self.widget_uid(), &scope.path, RepeatButtonAction::Started);
+            self.click(cx, scope);
+            self.current_interval = self.interval;
+            self.timer = cx.start_timeout(self.initial_delay);
+        }
+        // A `Button` reports being released over it as `Clicked`, and elsewhere as `Released`.
+        if button.clicked(actions) || button.released(actions) {
+            self.stop(cx, scope);
+        }
+    }
+}
+
+impl RepeatButton {
+    fn click(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        cx.widget_action(self.widget_uid(), &scope.path, ButtonAction::Clicked);
+    }
+
+    fn stop(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        if self.timer.is_empty() {
+            return;
+        }
+        cx.stop_timer(self.timer);
+        self.timer = Timer::empty();
+        cx.widget_action(self.widget_uid(), &scope.path, RepeatButtonAction::Stopped);
+    }
+}
+
+impl RepeatButtonRef {
+    // Whether the button is held down and repeating.
+    pub fn is_repeating(&self) -> bool {
+        self.borrow().is_some_and(|inner| !inner.timer.is_empty())
+    }
+}
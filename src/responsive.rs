@@ -0,0 +1,99 @@
+use makepad_widgets::*;
+use crate::debug_layout::InspectedLayout;
+
+// A view whose layout depends on the width of the window. Below `breakpoint` the children are
+// laid out with `narrow`, otherwise with `wide`:
+//
+//     InputView = <ResponsiveView> {
+//         breakpoint: 800,
+//         wide: {flow: Right, spacing: 10},
+//         narrow: {flow: Down, spacing: 4},
+//         ...
+//     }
+//
+// Only `flow`, `spacing`, `align` and `padding` of the layouts are applied, over those of the view
+// itself. The owner of the window calls `ResponsiveViewRef::set_width` when the window is resized
+// (see `App::set_window_width`); until then the view picks its layout from the size of the pass it
+// is first drawn in.
+live_design!{
+    import makepad_widgets::base::*;
+
+    ResponsiveView = {{ResponsiveView}} {
+        width: Fit,
+        height: Fit,
+        breakpoint: 800,
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct ResponsiveView {
+    #[deref] view: View,
+
+    // The narrowest window width, in logical pixels, that still gets the `wide` layout.
+    #[live] breakpoint: f64,
+    #[live] wide: Layout,
+    #[live] narrow: Layout,
+
+    // Whether `narrow` is applied, or `None` before the first `set_width`.
+    #[rust] narrow_applied: Option<bool>,
+}
+
+impl Widget for ResponsiveView {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        // Not resized yet.
+        if self.narrow_applied.is_none() {
+            let width = cx.current_pass_size().x;
+            self.set_width(cx, width);
+        }
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+impl ResponsiveView {
+    fn set_width(&mut self, cx: &mut Cx, width: f64) {
+        let narrow = width < self.breakpoint;
+        if self.narrow_applied == Some(narrow) {
+            return;
+        }
+        self.narrow_applied = Some(narrow);
+        let layout = if narrow {self.narrow} else {self.wide};
+        match layout.flow {
+            Flow::Right => self.view.apply_over(cx, live!{flow: Right}),
+            Flow::Down => self.view.apply_over(cx, live!{flow: Down}),
+            Flow::Overlay => self.view.apply_over(cx, live!{flow: Overlay}),
+            Flow::RightWrap => self.view.apply_over(cx, live!{flow: RightWrap}),
+        }
+        let padding = layout.padding;
+        self.view.apply_over(cx, live!{
+            spacing: (layout.spacing),
+            align: {x: (layout.align.x), y: (layout.align.y)},
+            padding: {left: (padding.left), top: (padding.top), right: (padding.right), bottom: (padding.bottom)},
+        });
+        self.view.redraw(cx);
+    }
+}
+
+impl ResponsiveViewRef {
+    // Applies the layout for a window of the given width, if it isn't applied already.
+    pub fn set_width(&self, cx: &mut Cx, width: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_width(cx, width);
+        }
+    }
+
+    // Whether the `narrow` layout is applied.
+    pub fn is_narrow(&self) -> bool {
+        self.borrow().is_some_and(|inner| inner.narrow_applied == Some(true))
+    }
+}
+
+// Reports the padding of the applied layout to the layout debug overlay, see `debug_layout.rs`.
+pub fn inspect_layout(_cx: &mut Cx, widget: &WidgetRef) -> Option<InspectedLayout> {
+    let inner = widget.borrow::<ResponsiveView>()?;
+    let layout = if inner.narrow_applied? {inner.narrow} else {inner.wide};
+    Some(InspectedLayout {padding: Some(layout.padding), children: Vec::new()})
+}
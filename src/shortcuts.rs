@@ -0,0 +1,135 @@
+use makepad_widgets::*;
+
+// Keyboard shortcuts driving a counter, for keyboard-only use and kiosk builds without a mouse.
+//
+// The keys are configured in the DSL with key names, several per command:
+//
+//     counter_shortcuts: {
+//         increment: ["Equals", "NumpadAdd", "ArrowUp"],
+//         decrement: ["Minus", "NumpadSubtract", "ArrowDown"],
+//         page_up: ["PageUp"],
+//         page_down: ["PageDown"],
+//         reset: ["Home"],
+//     }
+//
+// Names are those of the `KeyCode` variants, plus "+", "-", "Up", "Down", "Left", "Right" and
+// "Return" as aliases. Unknown names are logged and ignored. Shift is ignored, so "Equals" also
+// matches "+" on layouts where it is Shift+=. Keys pressed together with Ctrl, Alt or Cmd never
+// match, to leave those combinations to app-wide shortcuts.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CounterCommand {
+    // One step up or down.
+    Increment,
+    Decrement,
+    // One page step up or down.
+    PageUp,
+    PageDown,
+    // Back to the initial value.
+    Reset,
+}
+
+#[derive(Live)]
+pub struct CounterShortcuts {
+    #[live] increment: Vec<String>,
+    #[live] decrement: Vec<String>,
+    #[live] page_up: Vec<String>,
+    #[live] page_down: Vec<String>,
+    #[live] reset: Vec<String>,
+}
+
+impl LiveHook for CounterShortcuts {
+    fn after_apply(&mut self, _cx: &mut Cx, _from: ApplyFrom, _index: usize, _nodes: &[LiveNode]) {
+        for name in self.unknown_names() {
+            log!("counter_shortcuts: {:?} isn't a key name, the binding is ignored", name);
+        }
+    }
+}
+
+impl CounterShortcuts {
+    // The bound names that aren't key names, see `key_code_from_name`.
+    pub fn unknown_names(&self) -> Vec<&str> {
+        [&self.increment, &self.decrement, &self.page_up, &self.page_down, &self.reset]
+            .into_iter()
+            .flatten()
+            .map(|name| name.as_str())
+            .filter(|name| key_code_from_name(name).is_none())
+            .collect()
+    }
+
+
+    // The command bound to a key press, if any.
+    pub fn command(&self, ke: &KeyEvent) -> Option<CounterCommand> {
+        if ke.modifiers.control || ke.modifiers.alt || ke.modifiers.logo {
+            return None;
+        }
+        let bindings = [
+            (&self.increment, CounterCommand::Increment),
+            (&self.decrement, CounterCommand::Decrement),
+            (&self.page_up, CounterCommand::PageUp),
+            (&self.page_down, CounterCommand::PageDown),
+            (&self.reset, CounterCommand::Reset),
+        ];
+        bindings.into_iter().find_map(|(names, command)| {
+            names
+                .iter()
+                .any(|name| key_code_from_name(name) == Some(ke.key_code))
+                .then_some(command)
+        })
+    }
+}
+
+// The keys that can be bound, by name: the `KeyCode` variants except the modifier keys, plus a
+// few aliases.
+const KEY_NAMES: &[(&str, KeyCode)] = &[
+    ("Key0", KeyCode::Key0), ("Key1", KeyCode::Key1), ("Key2", KeyCode::Key2),
+    ("Key3", KeyCode::Key3), ("Key4", KeyCode::Key4),
+    ("Key5", KeyCode::Key5), ("Key6", KeyCode::Key6), ("Key7", KeyCode::Key7),
+    ("Key8", KeyCode::Key8), ("Key9", KeyCode::Key9),
+    ("KeyA", KeyCode::KeyA), ("KeyB", KeyCode::KeyB), ("KeyC", KeyCode::KeyC),
+    ("KeyD", KeyCode::KeyD), ("KeyE", KeyCode::KeyE),
+    ("KeyF", KeyCode::KeyF), ("KeyG", KeyCode::KeyG), ("KeyH", KeyCode::KeyH),
+    ("KeyI", KeyCode::KeyI), ("KeyJ", KeyCode::KeyJ),
+    ("KeyK", KeyCode::KeyK), ("KeyL", KeyCode::KeyL), ("KeyM", KeyCode::KeyM),
+    ("KeyN", KeyCode::KeyN), ("KeyO", KeyCode::KeyO),
+    ("KeyP", KeyCode::KeyP), ("KeyQ", KeyCode::KeyQ), ("KeyR", KeyCode::KeyR),
+    ("KeyS", KeyCode::KeyS), ("KeyT", KeyCode::KeyT),
+    ("KeyU", KeyCode::KeyU), ("KeyV", KeyCode::KeyV), ("KeyW", KeyCode::KeyW),
+    ("KeyX", KeyCode::KeyX), ("KeyY", KeyCode::KeyY),
+    ("KeyZ", KeyCode::KeyZ),
+    ("F1", KeyCode::F1), ("F2", KeyCode::F2), ("F3", KeyCode::F3), ("F4", KeyCode::F4),
+    ("F5", KeyCode::F5), ("F6", KeyCode::F6),
+    ("F7", KeyCode::F7), ("F8", KeyCode::F8), ("F9", KeyCode::F9), ("F10", KeyCode::F10),
+    ("F11", KeyCode::F11), ("F12", KeyCode::F12),
+    ("Numpad0", KeyCode::Numpad0), ("Numpad1", KeyCode::Numpad1), ("Numpad2", KeyCode::Numpad2),
+    ("Numpad3", KeyCode::Numpad3), ("Numpad4", KeyCode::Numpad4),
+    ("Numpad5", KeyCode::Numpad5), ("Numpad6", KeyCode::Numpad6), ("Numpad7", KeyCode::Numpad7),
+    ("Numpad8", KeyCode::Numpad8), ("Numpad9", KeyCode::Numpad9),
+    ("NumpadEquals", KeyCode::NumpadEquals), ("NumpadSubtract", KeyCode::NumpadSubtract),
+    ("NumpadAdd", KeyCode::NumpadAdd),
+    ("NumpadDecimal", KeyCode::NumpadDecimal), ("NumpadMultiply", KeyCode::NumpadMultiply),
+    ("NumpadDivide", KeyCode::NumpadDivide),
+    ("NumpadEnter", KeyCode::NumpadEnter), ("Escape", KeyCode::Escape),
+    ("Backtick", KeyCode::Backtick),
+    ("Minus", KeyCode::Minus), ("Equals", KeyCode::Equals), ("Backspace", KeyCode::Backspace),
+    ("Tab", KeyCode::Tab), ("LBracket", KeyCode::LBracket), ("RBracket", KeyCode::RBracket),
+    ("ReturnKey", KeyCode::ReturnKey), ("Semicolon", KeyCode::Semicolon), ("Quote", KeyCode::Quote),
+    ("Backslash", KeyCode::Backslash), ("Comma", KeyCode::Comma), ("Period", KeyCode::Period),
+    ("Slash", KeyCode::Slash), ("Space", KeyCode::Space), ("PrintScreen", KeyCode::PrintScreen),
+    ("ScrollLock", KeyCode::ScrollLock), ("Pause", KeyCode::Pause), ("Insert", KeyCode::Insert),
+    ("Delete", KeyCode::Delete), ("Home", KeyCode::Home), ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp), ("PageDown", KeyCode::PageDown), ("ArrowUp", KeyCode::ArrowUp),
+    ("ArrowDown", KeyCode::ArrowDown), ("ArrowLeft", KeyCode::ArrowLeft),
+    ("ArrowRight", KeyCode::ArrowRight),
+    // Aliases.
+    ("+", KeyCode::Equals), ("-", KeyCode::Minus), ("Up", KeyCode::ArrowUp),
+    ("Down", KeyCode::ArrowDown),
+    ("Left", KeyCode::ArrowLeft), ("Right", KeyCode::ArrowRight), ("Return", KeyCode::ReturnKey),
+];
+
+// The key code for a key name, see the top of this file. `None` for names that aren't keys, which
+// `CounterShortcuts` logs when it is applied.
+pub fn key_code_from_name(name: &str) -> Option<KeyCode> {
+    let name = name.trim();
+    KEY_NAMES.iter().find(|(n, _)| *n == name).map(|(_, key_code)| *key_code)
+}
@@ -0,0 +1,110 @@
+use makepad_widgets::*;
+use crate::debug_layout::{drawn_widgets, LayoutNode};
+
+// Headless layout snapshots: the rects of the widgets in a window's `body` after laying it out at
+// a given size, as JSON. Layout runs as part of drawing, so the body is drawn into a pass of its
+// own that is never rendered (see `HeadlessPass`), which works without a window or GPU. The tests
+// in `tests/layout_snapshots.rs` compare snapshots with golden files to catch layout regressions.
+//
+// The body is drawn on its own, without the window's caption bar, at a fixed DPI factor of 1.0.
+// The widgets are those of the drawn tree, see `drawn_widgets`, so the cells `AlignmentMatrix`
+// creates from Rust are included and widgets sharing an id are told apart by their parents.
+
+#[derive(Clone, Debug)]
+pub struct SnapshotEntry {
+    pub path: Vec<LiveId>,
+    // `None` if the widget wasn't drawn, e.g. because it is hidden.
+    pub rect: Option<Rect>,
+}
+
+#[derive(Clone, Debug)]
+pub struct LayoutSnapshot {
+    pub size: DVec2,
+    pub widgets: Vec<SnapshotEntry>,
+}
+
+// A pass the `body` of a window is drawn into without ever being rendered, so that its widgets
+// are laid out and get areas. Shared by `LayoutSnapshot::take` and `TestHarness::draw`.
+pub struct HeadlessPass {
+    pass: Pass,
+    draw_list: DrawList2d,
+    overlay: Overlay,
+}
+
+impl HeadlessPass {
+    pub fn new(cx: &mut Cx) -> Self {
+        Self {
+            pass: Pass::new(cx),
+            draw_list: DrawList2d::new(cx),
+            overlay: Overlay::new(cx),
+        }
+    }
+
+    // Lays out and draws the `body` of the window `ui` at `size`.
+    pub fn draw_body(&mut self, cx: &mut Cx, ui: &WidgetRef, size: DVec2) {
+        let body = ui.widget(id!(body));
+        self.pass.set_size(cx, size);
+        let draw_event = DrawEvent::default();
+        let cx = &mut Cx2d::new(cx, &draw_event);
+
+        cx.begin_pass(&self.pass, Some(1.0));
+        self.draw_list.begin_always(cx);
+        cx.begin_pass_sized_turtle(Layout::flow_down());
+        // Popups, such as the menu of a dropdown, are drawn into the overlay.
+        self.overlay.begin(cx);
+        let _ = body.draw_all(cx, &mut Scope::empty());
+        self.overlay.end(cx);
+        cx.end_pass_sized_turtle();
+        self.draw_list.end(cx);
+        cx.end_pass(&self.pass);
+    }
+}
+
+impl LayoutSnapshot {
+    // Lays out the `body` of the window `ui` at `size` in `pass`, and takes the rects of the
+    // widgets of `tree` under it.
+    pub fn take(cx: &mut Cx, pass: &mut HeadlessPass, ui: &WidgetRef, tree: &[LayoutNode], size: DVec2) -> Self {
+        pass.draw_body(cx, ui, size);
+        let widgets = drawn_widgets(cx, tree)
+            .into_iter()
+            .filter(|widget| widget.path.first() == Some(&live_id!(body)))
+            .map(|widget| SnapshotEntry {path: widget.path, rect: widget.rect})
+            .collect();
+        Self {size, widgets}
+    }
+
+    // The snapshot as JSON, one widget per line so that diffs of golden files are readable:
+    //
+    //     {
+    //       "size": [390, 844],
+    //       "widgets": [
+    //         {"id": "body.ButtonView", "rect": [139.5, 306, 111, 132]},
+    //         {"id": "body.PasswordView.toggle", "rect": null}
+    //       ]
+    //     }
+    //
+    // Coordinates are rounded to two decimals, so that the output is stable across platforms.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        json.push_str("{\n");
+        json.push_str(&format!("  \"size\": [{}, {}],\n", round(self.size.x), round(self.size.y)));
+        json.push_str("  \"widgets\": [\n");
+        for (i, widget) in self.widgets.iter().enumerate() {
+            let id = widget.path.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(".");
+            let rect = match widget.rect {
+                Some(r) => format!("[{}, {}, {}, {}]", round(r.pos.x), round(r.pos.y), round(r.size.x), round(r.size.y)),
+                None => "null".to_string(),
+            };
+            let comma = if i + 1 < self.widgets.len() {","} else {""};
+            json.push_str(&format!("    {{\"id\": \"{}\", \"rect\": {}}}{}\n", id, rect, comma));
+        }
+        json.push_str("  ]\n");
+        json.push_str("}\n");
+        json
+    }
+}
+
+fn round(value: f64) -> f64 {
+    // Adding 0.0 turns -0.0 into 0.0.
+    (value * 100.0).round() / 100.0 + 0.0
+}
@@ -0,0 +1,319 @@
+use makepad_widgets::*;
+use std::cell::Cell;
+use std::sync::Once;
+use crate::app::{live_design, App};
+use crate::launch::{AppMode, LaunchOptions};
+use crate::snapshot::{HeadlessPass, LayoutSnapshot};
+
+// A headless driver for `App`, used by the tests in `tests/`.
+//
+// The harness boots a `Cx` with the app's live design but without a window or GPU, feeds
+// synthetic events into `AppMain::handle_event`, and reads widget text back for assertions.
+//
+// Input goes through the same events the platform layer sends: `click` draws the window into a
+// pass that is never rendered, so that widgets have areas, and sends a finger down and up at the
+// center of the widget; `type_text` taps a text input and sends key and text input events; and
+// `select` picks a dropdown item with the arrow keys. Key focus changes are reported with
+// `KeyFocus` and `KeyFocusLost` events after each event, as the platform layer does. Widgets the
+// harness has no gesture for, such as sliders, can still be driven with `send_widget_action`.
+pub struct TestHarness {
+    cx: Box<Cx>,
+    app: App,
+    // The pass the body of the window is drawn into, see `draw`.
+    pass: HeadlessPass,
+    size: DVec2,
+    // The time of the last event, in seconds. Each finger event is a second after the previous
+    // one, so taps are never taken for double taps.
+    time: f64,
+    // When the current finger went down, if it is down.
+    finger_down_time: Option<f64>,
+}
+
+// Widgets react to actions by emitting new ones (e.g. data bindings), so every event is followed
+// by dispatching the resulting actions until the app settles. Actions still left after this many
+// rounds are taken for a feedback loop, and fail the test.
+const MAX_ACTION_ROUNDS: usize = 16;
+
+// The window size until `resize` is called, that of a desktop window.
+const DEFAULT_SIZE: DVec2 = DVec2 {x: 1280.0, y: 1080.0};
+
+impl TestHarness {
+    // Boots the app in widgets mode.
+    pub fn new() -> Self {
+        Self::with_mode(AppMode::Widgets)
+    }
+
+    pub fn with_mode(mode: AppMode) -> Self {
+        // The default options, rather than ones parsed from the arguments of the test runner.
+        static LAUNCH_OPTIONS: Once = Once::new();
+        LAUNCH_OPTIONS.call_once(|| LaunchOptions::init(LaunchOptions::default()));
+
+        let mut cx = Box::new(Cx::new(Box::new(|_, _| {})));
+        App::live_register(&mut cx);
+        live_design(&mut cx);
+        cx.live_expand();
+        cx.live_scan_dependencies();
+
+        let mut app = App::new_main(&mut cx);
+        if app.mode() != mode {
+            app.set_mode(&mut cx, mode);
+        }
+        // Tests must not pick up, or overwrite, the state of a previous run.
+        app.set_state_file(None);
+        let pass = HeadlessPass::new(&mut cx);
+        let mut harness = Self {
+            cx,
+            app,
+            pass,
+            size: DEFAULT_SIZE,
+            time: 0.0,
+            finger_down_time: None,
+        };
+        harness.send_event(&Event::Startup);
+        harness
+    }
+
+    pub fn cx(&mut self) -> &mut Cx {
+        &mut self.cx
+    }
+
+    pub fn app(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    // Runs `f` with both the context and the app, e.g. to call `App` methods that take a `Cx`,
+    // then dispatches any actions emitted by `f`.
+    pub fn with_app<R>(&mut self, f: impl FnOnce(&mut Cx, &mut App) -> R) -> R {
+        let app = &mut self.app;
+        let mut result = None;
+        let actions = self.cx.capture_actions(|cx| result = Some(f(cx, app)));
+        self.dispatch_actions(actions);
+        result.unwrap()
+    }
+
+    pub fn widget(&self, path: &[LiveId]) -> WidgetRef {
+        self.app.ui().widget(path)
+    }
+
+    // The text of the widget at `path`, e.g. a `Label` or `TextInput`.
+    pub fn text(&self, path: &[LiveId]) -> String {
+        self.widget(path).text()
+    }
+
+    // Sends an event to the app, dispatches any actions emitted while handling it, and reports a
+    // change of the key focus.
+    pub fn send_event(&mut self, event: &Event) {
+        self.dispatch(event);
+        self.cycle_key_focus();
+    }
+
+    fn dispatch(&mut self, event: &Event) {
+        let app = &mut self.app;
+        let actions = self.cx.capture_actions(|cx| app.handle_event(cx, event));
+        self.dispatch_actions(actions);
+    }
+
+    fn dispatch_actions(&mut self, mut actions: Actions) {
+        let app = &mut self.app;
+        for _ in 0..MAX_ACTION_ROUNDS {
+            if actions.is_empty() {
+                break;
+            }
+            actions = self.cx.capture_actions(|cx| app.handle_event(cx, &Event::Actions(actions)));
+        }
+        assert!(
+            actions.is_empty(),
+            "Actions still emitted after {} rounds, likely a feedback loop: {:?}",
+            MAX_ACTION_ROUNDS,
+            actions
+        );
+    }
+
+    // Sends `KeyFocusLost` and `KeyFocus` if the key focus was moved while handling an event.
+    fn cycle_key_focus(&mut self) {
+        if let Some((prev, focus)) = self.cx.keyboard.cycle_key_focus_changed() {
+            self.dispatch(&Event::KeyFocusLost(KeyFocusEvent {prev, focus}));
+            self.dispatch(&Event::KeyFocus(KeyFocusEvent {prev, focus}));
+        }
+    }
+
+    // Sends a widget action as if it had been emitted by the widget at `path`.
+    pub fn send_widget_action(&mut self, path: &[LiveId], action: impl WidgetActionTrait) {
+        let uid = self.widget(path).widget_uid();
+        let actions = self.cx.capture_actions(|cx| {
+            cx.widget_action(uid, &HeapLiveIdPath::default(), action)
+        });
+        self.send_event(&Event::Actions(actions));
+    }
+
+    // Lays out and draws the body of the window at the current window size, without rendering
+    // it, so that widgets have areas. Called before every finger event.
+    pub fn draw(&mut self) {
+        self.pass.draw_body(&mut self.cx, self.app.ui(), self.size);
+        // Widgets may take the key focus while drawing, e.g. the filter of an open combo box.
+        self.cycle_key_focus();
+    }
+
+    // The center of the widget at `path` in the window, after drawing it.
+    pub fn center(&mut self, path: &[LiveId]) -> DVec2 {
+        self.draw();
+        let area = self.widget(path).area();
+        assert!(area.is_valid(&self.cx), "{:?} isn't drawn, e.g. because it is hidden", path);
+        let rect = area.rect(&self.cx);
+        rect.pos + rect.size * 0.5
+    }
+
+    // Puts the finger down on the widget at `path`, and keeps it there until `release`.
+    pub fn press(&mut self, path: &[LiveId]) {
+        let abs = self.center(path);
+        self.finger_down(abs);
+    }
+
+    // Lifts the finger off the widget at `path`.
+    pub fn release(&mut self, path: &[LiveId]) {
+        let abs = self.center(path);
+        self.finger_up(abs);
+    }
+
+    // Lifts the finger outside of the window, e.g. after dragging it off a button.
+    pub fn release_outside(&mut self) {
+        self.finger_up(dvec2(-1.0, -1.0));
+    }
+
+    // Clicks the widget at `path`, e.g. a button, or a text input to give it the key focus.
+    pub fn click(&mut self, path: &[LiveId]) {
+        self.press(path);
+        self.release(path);
+    }
+
+    pub fn finger_down(&mut self, abs: DVec2) {
+        assert!(self.finger_down_time.is_none(), "the finger is already down");
+        self.time += 1.0;
+        self.finger_down_time = Some(self.time);
+        let digit_id = live_id!(mouse).into();
+        self.cx.fingers.alloc_digit(digit_id);
+        self.send_event(&Event::FingerDown(FingerDownEvent {
+            window_id: CxWindowPool::id_zero(),
+            abs,
+            digit_id,
+            device: DigitDevice::Mouse {button: 0},
+            tap_count: 1,
+            handled: Cell::new(Area::Empty),
+            sweep_lock: Cell::new(Area::Empty),
+            modifiers: KeyModifiers::default(),
+            time: self.time,
+        }));
+    }
+
+    pub fn finger_up(&mut self, abs: DVec2) {
+        let capture_time = self.finger_down_time.take().expect("the finger isn't down");
+        self.time += 1.0;
+        let digit_id = live_id!(mouse).into();
+        self.send_event(&Event::FingerUp(FingerUpEvent {
+            window_id: CxWindowPool::id_zero(),
+            abs,
+            capture_time,
+            digit_id,
+            device: DigitDevice::Mouse {button: 0},
+            tap_count: 1,
+            modifiers: KeyModifiers::default(),
+            time: self.time,
+        }));
+        self.cx.fingers.free_digit(digit_id);
+    }
+
+    // Replaces the text of the text input at `path` as the user would: clicks it, selects all of
+    // its text, and types `text` over it.
+    pub fn type_text(&mut self, path: &[LiveId], text: &str) {
+        self.click(path);
+        self.press_key(KeyCode::KeyA, primary_modifier());
+        if text.is_empty() {
+            self.press_key(KeyCode::Backspace, KeyModifiers::default());
+        } else {
+            self.send_event(&Event::TextInput(TextInputEvent {
+                input: text.to_string(),
+                replace_last: false,
+                was_paste: false,
+            }));
+        }
+    }
+
+    // Takes the key focus away from whichever widget has it.
+    pub fn blur(&mut self) {
+        self.cx.set_key_focus(Area::Empty);
+        self.cycle_key_focus();
+    }
+
+    // Selects the item at `index` in the dropdown at `path` as the user would with the keyboard:
+    // clicks the dropdown, which gives it the key focus, and steps to the item with the arrow keys.
+    pub fn select(&mut self, path: &[LiveId], index: usize) {
+        let dropdown = self.app.ui().drop_down(path);
+        self.click(path);
+        let current = dropdown.selected_item();
+        if index == current {
+            // Closes the popup again.
+            self.click(path);
+            return;
+        }
+        let key_code = if index > current {KeyCode::ArrowDown} else {KeyCode::ArrowUp};
+        for _ in 0..index.abs_diff(current) {
+            self.press_key(key_code, KeyModifiers::default());
+        }
+        assert_eq!(dropdown.selected_item(), index, "{:?} has no item {}", path, index);
+    }
+
+    // Presses and releases a key, after drawing the window as the platform layer would have done
+    // since the previous event.
+    pub fn press_key(&mut self, key_code: KeyCode, modifiers: KeyModifiers) {
+        self.draw();
+        self.time += 1.0;
+        let key_event = KeyEvent {
+            key_code,
+            modifiers,
+            is_repeat: false,
+            time: self.time,
+        };
+        self.send_event(&Event::KeyDown(key_event.clone()));
+        self.send_event(&Event::KeyUp(key_event));
+    }
+
+    // Lays out the body of the window at the given size, without rendering it, after resizing the
+    // window so that responsive views pick their layout. See `snapshot.rs`.
+    pub fn layout_snapshot(&mut self, width: f64, height: f64) -> LayoutSnapshot {
+        self.resize(width, height);
+        let tree = self.app.layout_tree(&mut self.cx);
+        LayoutSnapshot::take(&mut self.cx, &mut self.pass, self.app.ui(), &tree, dvec2(width, height))
+    }
+
+    // Resizes the window, as the platform layer reports it.
+    pub fn resize(&mut self, width: f64, height: f64) {
+        let old_geom = WindowGeom {
+            inner_size: self.size,
+            ..WindowGeom::default()
+        };
+        self.size = dvec2(width, height);
+        self.send_event(&Event::WindowGeomChange(WindowGeomChangeEvent {
+            window_id: CxWindowPool::id_zero(),
+            old_geom,
+            new_geom: WindowGeom {
+                inner_size: self.size,
+                ..WindowGeom::default()
+            },
+        }));
+    }
+}
+
+impl Default for TestHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The modifier of shortcuts such as select all: command on macOS, control elsewhere.
+fn primary_modifier() -> KeyModifiers {
+    if cfg!(target_os = "macos") {
+        KeyModifiers {logo: true, ..Default::default()}
+    } else {
+        KeyModifiers {control: true, ..Default::default()}
+    }
+}
@@ -0,0 +1,109 @@
+use makepad_widgets::*;
+
+// A multi-line text input for notes and other longer text.
+//
+// The inner `input` is a regular `TextInput` with word wrapping, inside a view that scrolls
+// vertically once the text is taller than the area. Selection, copy and paste come from the
+// `TextInput`. Return, which a `TextInput` reports as `TextInputAction::Return`, inserts a newline
+// at the cursor instead: while the input has the keyboard focus, the key press is turned into a
+// text input event of "\n", which the input handles like any typed character.
+//
+//     notes = <TextArea> {
+//         height: 160,
+//         input = { empty_message: "Notes" }
+//     }
+//
+// `text()` returns the whole text, and changes are reported by the inner `input` with
+// `TextInputAction::Change` as usual. See `text_stats` for line, word and character counts.
+live_design!{
+    import makepad_widgets::base::*;
+    import makepad_widgets::theme_desktop_dark::*;
+
+    TextArea = {{TextArea}} {
+        width: 300,
+        height: 120,
+        flow: Down,
+        show_bg: true,
+        draw_bg: {
+            color: #333
+        }
+        scroll_bars: <ScrollBars> {
+            show_scroll_x: false,
+            show_scroll_y: true,
+        }
+
+        input = <TextInput> {
+            width: Fill,
+            height: Fit,
+            draw_text: {
+                wrap: Word,
+            }
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct TextArea {
+    #[deref] view: View,
+
+    // Whether the inner input has the keyboard focus, so Return is meant for it.
+    #[rust] focused: bool,
+}
+
+impl Widget for TextArea {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if let Event::KeyDown(ke) = event {
+            if self.focused && ke.key_code == KeyCode::ReturnKey {
+                let newline = Event::TextInput(TextInputEvent {
+                    input: "\n".to_string(),
+                    replace_last: false,
+                    was_paste: false,
+                });
+                self.view.handle_event(cx, &newline, scope);
+                return;
+            }
+        }
+        self.view.handle_event(cx, event, scope);
+        self.widget_match_event(cx, event, scope);
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+
+    fn text(&self) -> String {
+        self.text_input(id!(input)).text()
+    }
+
+    fn set_text(&mut self, v: &str) {
+        self.text_input(id!(input)).set_text(v);
+    }
+}
+
+impl WidgetMatchEvent for TextArea {
+    fn handle_actions(&mut self, _cx: &mut Cx, actions: &Actions, _scope: &mut Scope) {
+        let uid = self.text_input(id!(input)).widget_uid();
+        match actions.find_widget_action_cast(uid) {
+            TextInputAction::KeyFocus => self.focused = true,
+            TextInputAction::KeyFocusLost => self.focused = false,
+            _ => ()
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TextStats {
+    pub lines: usize,
+    pub words: usize,
+    pub chars: usize,
+}
+
+// Counts the lines, whitespace separated words and characters of a text. An empty text has no
+// lines, any other text one more than it has newlines.
+pub fn text_stats(text: &str) -> TextStats {
+    TextStats {
+        lines: if text.is_empty() {0} else {text.matches('\n').count() + 1},
+        words: text.split_whitespace().count(),
+        chars: text.chars().count(),
+    }
+}
@@ -0,0 +1,161 @@
+use makepad_widgets::*;
+use crate::dropdown_items::DropDownItem;
+use crate::text_area::text_stats;
+
+// Transforms applied to text on its way from one widget to another, e.g. from `input_sample` to
+// `label_input`. A transform implements `TextTransform` and is added to a `TransformRegistry`,
+// whose items fill the dropdown the transform is picked from:
+//
+//     struct Rot13;
+//
+//     impl TextTransform for Rot13 {
+//         fn id(&self) -> LiveId { live_id!(rot13) }
+//         fn label(&self) -> &str { "ROT13" }
+//         fn apply(&self, text: &str) -> String { ... }
+//     }
+//
+//     app.register_transform(cx, Box::new(Rot13));
+
+pub trait TextTransform {
+    // Identifies the transform, as the value of its dropdown item.
+    fn id(&self) -> LiveId;
+    // The name shown in the dropdown.
+    fn label(&self) -> &str;
+    fn apply(&self, text: &str) -> String;
+}
+
+#[derive(Default)]
+pub struct TransformRegistry {
+    transforms: Vec<Box<dyn TextTransform>>,
+}
+
+impl TransformRegistry {
+    // A registry with the built-in transforms, starting with `Identity`.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::default();
+        registry.register(Box::new(Identity));
+        registry.register(Box::new(Uppercase));
+        registry.register(Box::new(Lowercase));
+        registry.register(Box::new(Reverse));
+        registry.register(Box::new(Slugify));
+        registry.register(Box::new(Count));
+        registry.register(Box::new(Base64));
+        registry
+    }
+
+    // Adds a transform, or replaces the one with the same id.
+    pub fn register(&mut self, transform: Box<dyn TextTransform>) {
+        match self.transforms.iter().position(|t| t.id() == transform.id()) {
+            Some(index) => self.transforms[index] = transform,
+            None => self.transforms.push(transform),
+        }
+    }
+
+    pub fn get(&self, id: LiveId) -> Option<&dyn TextTransform> {
+        self.transforms.iter().find(|t| t.id() == id).map(|t| t.as_ref())
+    }
+
+    // Applies the transform with the given id, or leaves the text as is if there is none.
+    pub fn apply(&self, id: LiveId, text: &str) -> String {
+        match self.get(id) {
+            Some(transform) => transform.apply(text),
+            None => text.to_string(),
+        }
+    }
+
+    // The dropdown items for the registered transforms, in registration order.
+    pub fn items(&self) -> Vec<DropDownItem> {
+        self.transforms.iter().map(|t| DropDownItem::new(t.id(), t.label())).collect()
+    }
+}
+
+pub struct Identity;
+
+impl TextTransform for Identity {
+    fn id(&self) -> LiveId {live_id!(identity)}
+    fn label(&self) -> &str {"As typed"}
+    fn apply(&self, text: &str) -> String {
+        text.to_string()
+    }
+}
+
+pub struct Uppercase;
+
+impl TextTransform for Uppercase {
+    fn id(&self) -> LiveId {live_id!(uppercase)}
+    fn label(&self) -> &str {"UPPERCASE"}
+    fn apply(&self, text: &str) -> String {
+        text.to_uppercase()
+    }
+}
+
+pub struct Lowercase;
+
+impl TextTransform for Lowercase {
+    fn id(&self) -> LiveId {live_id!(lowercase)}
+    fn label(&self) -> &str {"lowercase"}
+    fn apply(&self, text: &str) -> String {
+        text.to_lowercase()
+    }
+}
+
+pub struct Reverse;
+
+impl TextTransform for Reverse {
+    fn id(&self) -> LiveId {live_id!(reverse)}
+    fn label(&self) -> &str {"Reversed"}
+    fn apply(&self, text: &str) -> String {
+        text.chars().rev().collect()
+    }
+}
+
+// Lowercase words joined with dashes, e.g. "Hello, World!" gives "hello-world".
+pub struct Slugify;
+
+impl TextTransform for Slugify {
+    fn id(&self) -> LiveId {live_id!(slugify)}
+    fn label(&self) -> &str {"Slug"}
+    fn apply(&self, text: &str) -> String {
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+}
+
+pub struct Count;
+
+impl TextTransform for Count {
+    fn id(&self) -> LiveId {live_id!(count)}
+    fn label(&self) -> &str {"Count"}
+    fn apply(&self, text: &str) -> String {
+        let stats = text_stats(text);
+        format!("{} characters, {} words", stats.chars, stats.words)
+    }
+}
+
+// Standard base64 of the UTF-8 bytes, with padding.
+pub struct Base64;
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+impl TextTransform for Base64 {
+    fn id(&self) -> LiveId {live_id!(base64)}
+    fn label(&self) -> &str {"Base64"}
+    fn apply(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len().div_ceil(3) * 4);
+        for chunk in text.as_bytes().chunks(3) {
+            let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+            let bits = ((bytes[0] as u32) << 16) | ((bytes[1] as u32) << 8) | bytes[2] as u32;
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    out.push(BASE64_ALPHABET[((bits >> (18 - 6 * i)) & 0x3f) as usize] as char);
+                } else {
+                    out.push('=');
+                }
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,121 @@
+use makepad_widgets::*;
+use crate::dropdown_items::DropDownItemsExt;
+
+// Binding a `DropDown` to a Rust enum instead of raw `LiveId`s.
+//
+// A dropdown declares its items as `values` (ids) and `labels` in the DSL. An enum implementing
+// `DropDownEnum` maps each of those ids onto a variant, and the `DropDownEnumExt` methods on
+// `DropDownRef` then report selection changes as, and select items from, variants of that enum.
+//
+// The `drop_down_enum!` macro declares such an enum together with its `DropDownEnum` impl:
+//
+//     drop_down_enum!{
+//         pub enum Fruit {
+//             Apple = apple,
+//             Pear = pear,
+//         }
+//     }
+//
+// matching a dropdown with `values: [apple, pear]`. The selected item is mapped onto a variant by
+// its value if the items were set from Rust, see `dropdown_items.rs`. The values of items declared
+// in the DSL can't be read back from the dropdown, so those are mapped by index, and the variants
+// are listed in the same order as the values. The macro only refers to items by their full paths,
+// so it needs no imports where it is used.
+
+pub trait DropDownEnum: Copy + Sized + 'static {
+    // All variants, in the order of the dropdown values.
+    const ALL: &'static [Self];
+    // The variant for a dropdown value, if there is one.
+    fn from_live_id(id: LiveId) -> Option<Self>;
+    // The dropdown value of this variant.
+    fn live_id(self) -> LiveId;
+}
+
+#[macro_export]
+macro_rules! drop_down_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($variant:ident = $value:ident),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        $vis enum $name {
+            $($variant),*
+        }
+
+        impl $crate::typed_dropdown::DropDownEnum for $name {
+            const ALL: &'static [Self] = &[$(Self::$variant),*];
+
+            fn from_live_id(id: $crate::makepad_widgets::LiveId) -> Option<Self> {
+                $(
+                    if id == $crate::makepad_widgets::LiveId::from_str(stringify!($value)) {
+                        return Some(Self::$variant);
+                    }
+                )*
+                None
+            }
+
+            fn live_id(self) -> $crate::makepad_widgets::LiveId {
+                match self {
+                    $(Self::$variant => $crate::makepad_widgets::LiveId::from_str(stringify!($value))),*
+                }
+            }
+        }
+    };
+}
+
+// Reads a value written to a `DataBindingStore` by a dropdown bound at `path`.
+pub fn read_enum<T: DropDownEnum>(store: &DataBindingStore, path: &[LiveId]) -> Option<T> {
+    if let Some(LiveValue::Id(id)) = store.nodes.read_field_value(path) {
+        T::from_live_id(*id)
+    } else {
+        None
+    }
+}
+
+pub trait DropDownEnumExt {
+    // The selected variant, if the selection was changed by one of the given actions.
+    fn changed_enum<T: DropDownEnum>(&self, actions: &Actions) -> Option<T>;
+    // The variant of the selected item, `None` if it has no variant.
+    fn selected_enum<T: DropDownEnum>(&self) -> Option<T>;
+    // Selects the item for the given variant and redraws the dropdown.
+    fn set_selected_enum<T: DropDownEnum>(&self, cx: &mut Cx, value: T);
+}
+
+impl DropDownEnumExt for DropDownRef {
+    fn changed_enum<T: DropDownEnum>(&self, actions: &Actions) -> Option<T> {
+        if let DropDownAction::Select(_, LiveValue::Id(id)) = actions.find_widget_action_cast(self.widget_uid()) {
+            T::from_live_id(id)
+        } else {
+            None
+        }
+    }
+
+    fn selected_enum<T: DropDownEnum>(&self) -> Option<T> {
+        let value = match self.selected_value() {
+            Some(value) => value,
+            // Items from the DSL, declared in the order of the variants.
+            None => match T::ALL.get(self.selected_item()) {
+                Some(variant) => return Some(*variant),
+                None => {
+                    log!("Item {} of the dropdown has no variant in {}", self.selected_item(), std::any::type_name::<T>());
+                    return None;
+                }
+            }
+        };
+        let variant = T::from_live_id(value);
+        if variant.is_none() {
+            log!("The dropdown value {} has no variant in {}", value, std::any::type_name::<T>());
+        }
+        variant
+    }
+
+    fn set_selected_enum<T: DropDownEnum>(&self, cx: &mut Cx, value: T) {
+        // The dropdown maps values onto items itself when data is bound to it, so reuse that.
+        let mut store = DataBindingStore::new();
+        store.nodes.write_field_value(&[live_id!(value)], LiveValue::Id(value.live_id()));
+        self.data_to_widget(cx, &store.nodes, &[live_id!(value)]);
+    }
+}
@@ -0,0 +1,271 @@
+use makepad_widgets::*;
+use crate::counter::{is_bounded, round_to};
+use regex::Regex;
+
+// A `TextInput` with validation. The text is checked against a list of validators every time it
+// changes. While it is invalid, the input's background turns `error_color` and the message of the
+// first failing validator is shown in the `error` label underneath.
+//
+// The common validators can be declared in the DSL:
+//
+//     email = <ValidatedInput> {
+//         required: true,
+//         max_length: 64,
+//         pattern: "^[^@]+@[^@]+$",
+//         pattern_message: "Not an email address",
+//     }
+//     age = <ValidatedInput> {
+//         numeric: true, min: 0, max: 150,
+//     }
+//
+// and any validator, including custom closures, can be added from Rust with
+// `ValidatedInputRef::add_validator`. Use `is_valid` to check the input before submitting a form.
+live_design!{
+    import makepad_widgets::base::*;
+    import makepad_widgets::theme_desktop_dark::*;
+
+    ValidatedInput = {{ValidatedInput}} {
+        width: Fit,
+        height: Fit,
+        flow: Down,
+        spacing: 4,
+        normal_color: #333,
+        error_color: #x7A2A2A,
+        pattern_message: "Invalid format",
+
+        input = <TextInput> {
+            draw_bg: {
+                color: #333
+            }
+        }
+
+        error = <Label> {
+            draw_text: {
+                text_style: { font_size: 10 }
+                color: #xFF6B6B
+            },
+            text: ""
+        }
+    }
+}
+
+pub enum Validator {
+    // The text must not be empty or only whitespace.
+    Required,
+    // The text must have at most this many characters.
+    MaxLength(usize),
+    // The text must be a number within `min..=max`.
+    Range(f64, f64),
+    // The text must be a number within `min..=max` once rounded to this many decimals, as a
+    // `CounterWidget` with that `precision` rounds it.
+    RoundedRange(f64, f64, usize),
+    // The text must match the regular expression. The string is the error message.
+    Regex(Regex, String),
+    // The closure returns the error message for invalid text.
+    Custom(Box<dyn Fn(&str) -> Result<(), String>>),
+}
+
+impl Validator {
+    pub fn custom(f: impl Fn(&str) -> Result<(), String> + 'static) -> Self {
+        Self::Custom(Box::new(f))
+    }
+
+    pub fn validate(&self, text: &str) -> Result<(), String> {
+        match self {
+            Self::Required => {
+                if text.trim().is_empty() {
+                    return Err("This field is required".to_string());
+                }
+            }
+            Self::MaxLength(max) => {
+                if text.chars().count() > *max {
+                    return Err(format!("At most {} characters", max));
+                }
+            }
+            Self::Range(min, max) => check_range(text, *min, *max, None)?,
+            Self::RoundedRange(min, max, precision) => check_range(text, *min, *max, Some(*precision))?,
+            Self::Regex(regex, message) => {
+                if !text.is_empty() && !regex.is_match(text) {
+                    return Err(message.clone());
+                }
+            }
+            Self::Custom(f) => f(text)?,
+        }
+        Ok(())
+    }
+}
+
+// Checks that `text` is a number within `min..=max`, after rounding it to `precision` decimals.
+fn check_range(text: &str, min: f64, max: f64, precision: Option<usize>) -> Result<(), String> {
+    // An empty input is left to `Required`.
+    if text.trim().is_empty() {
+        return Ok(());
+    }
+    let value = text
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| "Must be a number".to_string())?;
+    let value = precision.map_or(value, |precision| round_to(value, precision));
+    if value < min || value > max {
+        return Err(match (is_bounded(min), is_bounded(max)) {
+            (true, false) => format!("Must be at least {}", min),
+            (false, true) => format!("Must be at most {}", max),
+            _ => format!("Must be between {} and {}", min, max),
+        });
+    }
+    Ok(())
+}
+
+#[derive(Live, Widget)]
+pub struct ValidatedInput {
+    #[deref] view: View,
+
+    #[live] required: bool,
+    // Zero means no limit.
+    #[live] max_length: usize,
+    // Whether the text must be a number within `min..=max`. Either bound may be left out.
+    #[live] numeric: bool,
+    #[live(f64::MIN)] min: f64,
+    #[live(f64::MAX)] max: f64,
+    // A regular expression the text must match, if not empty.
+    #[live] pattern: String,
+    #[live] pattern_message: String,
+
+    #[live] normal_color: Vec4,
+    #[live] error_color: Vec4,
+
+    // The validators declared in the DSL, the range set with `set_numeric_range`, and those added
+    // from Rust, checked in that order.
+    #[rust] dsl_validators: Vec<Validator>,
+    #[rust] range_validator: Option<Validator>,
+    #[rust] validators: Vec<Validator>,
+    #[rust] error: Option<String>,
+}
+
+impl LiveHook for ValidatedInput {
+    // Runs on creation and whenever the live properties change, e.g. on live reload or with
+    // `apply_over`, so the validators always match them.
+    fn after_apply(&mut self, _cx: &mut Cx, _from: ApplyFrom, _index: usize, _nodes: &[LiveNode]) {
+        self.build_dsl_validators();
+    }
+}
+
+impl Widget for ValidatedInput {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+        self.widget_match_event(cx, event, scope);
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+
+    fn text(&self) -> String {
+        self.text_input(id!(input)).text()
+    }
+
+    fn set_text(&mut self, v: &str) {
+        self.text_input(id!(input)).set_text(v);
+    }
+}
+
+impl WidgetMatchEvent for ValidatedInput {
+    fn handle_actions(&mut self, cx: &mut Cx, actions: &Actions, _scope: &mut Scope) {
+        if self.text_input(id!(input)).changed(actions).is_some() {
+            self.validate(cx);
+        }
+    }
+}
+
+impl ValidatedInput {
+    // Builds the validators declared with the live properties.
+    fn build_dsl_validators(&mut self) {
+        self.dsl_validators.clear();
+        if self.required {
+            self.dsl_validators.push(Validator::Required);
+        }
+        if self.max_length > 0 {
+            self.dsl_validators.push(Validator::MaxLength(self.max_length));
+        }
+        if self.numeric {
+            self.dsl_validators.push(Validator::Range(self.min, self.max));
+        }
+        if !self.pattern.is_empty() {
+            match Regex::new(&self.pattern) {
+                Ok(regex) => self.dsl_validators.push(Validator::Regex(regex, self.pattern_message.clone())),
+                Err(err) => log!("Invalid pattern \"{}\" in ValidatedInput: {}", self.pattern, err),
+            }
+        }
+    }
+
+    // The message of the first validator the text fails, if any.
+    fn check(&self) -> Option<String> {
+        let text = self.text();
+        self.dsl_validators
+            .iter()
+            .chain(&self.range_validator)
+            .chain(self.validators.iter())
+            .find_map(|validator| validator.validate(&text).err())
+    }
+
+    // Validates the text and shows the result.
+    pub fn validate(&mut self, cx: &mut Cx) -> bool {
+        let error = self.check();
+        if error != self.error {
+            let color = if error.is_some() {self.error_color} else {self.normal_color};
+            self.text_input(id!(input)).apply_over(cx, live!{
+                draw_bg: {color: (color)}
+            });
+            self.label(id!(error)).set_text_and_redraw(cx, error.as_deref().unwrap_or(""));
+            self.error = error;
+            self.redraw(cx);
+        }
+        self.error.is_none()
+    }
+}
+
+impl ValidatedInputRef {
+    pub fn add_validator(&self, validator: Validator) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.validators.push(validator);
+        }
+    }
+
+    // Whether the current text passes all validators. Doesn't change what is shown.
+    pub fn is_valid(&self) -> bool {
+        if let Some(inner) = self.borrow() {
+            inner.check().is_none()
+        } else {
+            true
+        }
+    }
+
+    // Validates the text and shows the result, e.g. to flag empty required fields on submit.
+    pub fn validate(&self, cx: &mut Cx) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.validate(cx)
+        } else {
+            true
+        }
+    }
+
+    // Requires the text to be a number within `min..=max` once rounded to `precision` decimals,
+    // given as `(min, max, precision)`, or lifts that requirement with `None`. Applies on top of
+    // the `numeric`, `min` and `max` live properties, which are left as declared. Revalidates the
+    // text.
+    pub fn set_numeric_range(&self, cx: &mut Cx, range: Option<(f64, f64, usize)>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.range_validator = range.map(|(min, max, precision)| {
+                Validator::RoundedRange(min, max, precision)
+            });
+            inner.validate(cx);
+        }
+    }
+
+    // The error shown for the last validation, if any.
+    pub fn error(&self) -> Option<String> {
+        self.borrow().and_then(|inner| inner.error.clone())
+    }
+}
@@ -0,0 +1,49 @@
+use makepad_widgets::*;
+use makepad_widgets_sample::app::LayoutSection;
+use makepad_widgets_sample::alignment_matrix::*;
+use makepad_widgets_sample::launch::AppMode;
+use makepad_widgets_sample::test_harness::TestHarness;
+
+#[test]
+fn alignments() {
+    assert_eq!(alignment(0, 3), 0.0);
+    assert_eq!(alignment(1, 3), 0.5);
+    assert_eq!(alignment(2, 3), 1.0);
+    assert_eq!(alignment(0, 1), 0.5);
+    assert_eq!(alignment(3, 5), 0.75);
+}
+
+#[test]
+fn matrices_cover_every_alignment() {
+    // The matrices are a section of `LayoutView`.
+    let mut harness = TestHarness::with_mode(AppMode::Layout);
+    harness.click(id!(alignment_tab));
+    assert_eq!(harness.app().layout_section(), LayoutSection::Alignment);
+    let down = harness.app().ui().alignment_matrix(id!(down_matrix));
+    let labels = down.cell_labels();
+    assert_eq!(labels.len(), 9);
+    assert_eq!(labels[0], "Down\nx: 0, y: 0");
+    assert_eq!(labels[5], "Down\nx: 1, y: 0.5");
+
+    let right = harness.app().ui().alignment_matrix(id!(right_matrix));
+    assert_eq!(right.cell_labels()[4], "Right\nx: 0.5, y: 0.5");
+
+    right.set_size(harness.cx(), 2, 4);
+    let labels = right.cell_labels();
+    assert_eq!(labels.len(), 8);
+    assert_eq!(labels[7], "Right\nx: 1, y: 1");
+}
+
+#[test]
+fn applying_rebuilds_the_cells() {
+    let mut harness = TestHarness::with_mode(AppMode::Layout);
+    harness.click(id!(alignment_tab));
+    let down = harness.app().ui().alignment_matrix(id!(down_matrix));
+    down.apply_over(harness.cx(), live!{rows: 2, columns: 2, cell_flow: Right});
+    assert_eq!(down.cell_labels(), vec![
+        "Right\nx: 0, y: 0",
+        "Right\nx: 1, y: 0",
+        "Right\nx: 0, y: 1",
+        "Right\nx: 1, y: 1",
+    ]);
+}
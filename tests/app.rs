@@ -0,0 +1,541 @@
+use makepad_widgets::*;
+use makepad_widgets_sample::app::{Selection, ShellPage};
+use makepad_widgets_sample::counter::*;
+use makepad_widgets_sample::dropdown_items::*;
+use makepad_widgets_sample::launch::AppMode;
+use makepad_widgets_sample::multi_select::*;
+use makepad_widgets_sample::persist::read_ids;
+use makepad_widgets_sample::reactive::Reactor;
+use makepad_widgets_sample::password_input::*;
+use makepad_widgets_sample::repeat_button::*;
+use makepad_widgets_sample::validation::*;
+use std::collections::HashSet;
+use makepad_widgets_sample::test_harness::TestHarness;
+
+#[test]
+fn counter_buttons_update_label() {
+    let mut harness = TestHarness::new();
+    assert_eq!(harness.text(id!(label1)), "Label: 0");
+
+    harness.click(id!(button1));
+    harness.click(id!(button1));
+    assert_eq!(harness.text(id!(label1)), "Label: 2");
+
+    harness.click(id!(button2));
+    assert_eq!(harness.text(id!(label1)), "Label: 1");
+}
+
+#[test]
+fn counter_does_not_go_below_zero() {
+    let mut harness = TestHarness::new();
+    harness.click(id!(button2));
+    assert_eq!(harness.text(id!(label1)), "Label: 0");
+}
+
+#[test]
+fn counter_overflow_policies() {
+    let mut harness = TestHarness::new();
+    let counter = harness.widget(id!(counter));
+    counter.apply_over(harness.cx(), live!{max: 2.0, overflow: Wrap});
+    harness.click(id!(button2));
+    assert_eq!(harness.text(id!(label1)), "Label: 2");
+
+    counter.apply_over(harness.cx(), live!{overflow: Reject});
+    harness.click(id!(button1));
+    assert_eq!(harness.text(id!(label1)), "Label: 2");
+    assert!(harness.app().ui().counter_widget(id!(counter)).is_shaking());
+}
+
+#[test]
+fn counter_bounds_are_validated() {
+    let mut harness = TestHarness::new();
+    let counter = harness.widget(id!(counter));
+    counter.apply_over(harness.cx(), live!{min: 5.0, max: 1.0});
+    assert_eq!(harness.app().ui().counter_widget(id!(counter)).range(), (1.0, 5.0));
+    // The value was 0, below the new bounds.
+    assert_eq!(harness.app().ui().counter_widget(id!(counter)).value(), 1.0);
+    assert_eq!(harness.text(id!(label1)), "Label: 1");
+    harness.click(id!(button1));
+    assert_eq!(harness.text(id!(label1)), "Label: 2");
+}
+
+#[test]
+fn counter_fractional_steps() {
+    let mut harness = TestHarness::new();
+    let counter = harness.widget(id!(counter));
+    counter.apply_over(harness.cx(), live!{step: 0.1, precision: 1});
+    for _ in 0..3 {
+        harness.click(id!(button1));
+    }
+    assert_eq!(harness.text(id!(label1)), "Label: 0.3");
+    assert_eq!(harness.app().ui().counter_widget(id!(counter)).value(), 0.3);
+}
+
+#[test]
+fn holding_counter_button_repeats() {
+    let mut harness = TestHarness::new();
+    let repeat_button = harness.app().ui().repeat_button(id!(button1));
+
+    // Pressing clicks right away, then repeats until released.
+    harness.press(id!(button1));
+    assert_eq!(harness.text(id!(label1)), "Label: 1");
+    assert!(repeat_button.is_repeating());
+
+    harness.release_outside();
+    assert!(!repeat_button.is_repeating());
+    assert_eq!(harness.text(id!(label1)), "Label: 1");
+}
+
+#[test]
+fn releasing_counter_button_over_it_stops_repeats() {
+    let mut harness = TestHarness::new();
+    let repeat_button = harness.app().ui().repeat_button(id!(button1));
+
+    // Released over the button, which the button reports as a click rather than a release.
+    harness.press(id!(button1));
+    assert!(repeat_button.is_repeating());
+    harness.release(id!(button1));
+    assert!(!repeat_button.is_repeating());
+    assert_eq!(harness.text(id!(label1)), "Label: 1");
+
+    // Losing the app focus stops the repeats too.
+    harness.press(id!(button1));
+    assert!(repeat_button.is_repeating());
+    harness.send_event(&Event::AppLostFocus);
+    assert!(!repeat_button.is_repeating());
+    harness.release_outside();
+}
+
+#[test]
+fn one_press_of_counter_button_is_one_undo_entry() {
+    let mut harness = TestHarness::new();
+    harness.click(id!(button1));
+
+    // Repeats sent as the clicks the timer would fire.
+    harness.press(id!(button1));
+    harness.send_widget_action(id!(button1), ButtonAction::Clicked);
+    harness.send_widget_action(id!(button1), ButtonAction::Clicked);
+    harness.release(id!(button1));
+    assert_eq!(harness.text(id!(label1)), "Label: 4");
+
+    harness.click(id!(undo_button));
+    assert_eq!(harness.text(id!(label1)), "Label: 1");
+    harness.click(id!(undo_button));
+    assert_eq!(harness.text(id!(label1)), "Label: 0");
+}
+
+#[test]
+fn counter_widget_ref() {
+    let mut harness = TestHarness::new();
+    let counter = harness.app().ui().counter_widget(id!(counter));
+    harness.click(id!(button1));
+    assert_eq!(counter.value(), 1.0);
+
+    counter.set_value(harness.cx(), 5.0);
+    assert_eq!(harness.text(id!(label1)), "Label: 5");
+
+    counter.set_value(harness.cx(), -3.0);
+    assert_eq!(counter.value(), 0.0);
+}
+
+#[test]
+fn input_starts_empty_with_placeholder() {
+    let harness = TestHarness::new();
+    assert_eq!(harness.text(id!(input_sample)), "");
+    assert_eq!(harness.text(id!(label_input)), "Input: ");
+}
+
+#[test]
+fn input_is_mirrored_to_label() {
+    let mut harness = TestHarness::new();
+    harness.type_text(id!(input_sample.input), "hello");
+    assert_eq!(harness.text(id!(input_sample)), "hello");
+    assert_eq!(harness.text(id!(label_input)), "Input: hello");
+}
+
+#[test]
+fn input_is_transformed_for_label() {
+    let mut harness = TestHarness::new();
+    harness.type_text(id!(input_sample.input), "Hello World");
+    harness.select(id!(my_dropdown.dropdown), 4);
+    assert_eq!(harness.text(id!(label_input)), "Input: hello-world");
+
+    harness.type_text(id!(input_sample.input), "abc");
+    assert_eq!(harness.text(id!(label_input)), "Input: abc");
+
+    harness.select(id!(my_dropdown.dropdown), 6);
+    assert_eq!(harness.text(id!(label_input)), "Input: YWJj");
+}
+
+#[test]
+fn only_dependents_of_changed_widgets_are_redrawn() {
+    let mut harness = TestHarness::new();
+    let before = harness.app().reactor().total_redraws();
+
+    // The counter has no reactive dependents.
+    harness.click(id!(button1));
+    assert_eq!(harness.app().reactor().total_redraws(), before);
+
+    // The input only redraws `label_input`.
+    harness.type_text(id!(input_sample.input), "hello");
+    assert_eq!(harness.app().reactor().total_redraws(), before + 1);
+}
+
+#[test]
+fn missing_dependents_are_not_counted() {
+    let mut harness = TestHarness::new();
+    let ui = harness.app().ui().clone();
+    let mut reactor = Reactor::<()>::default();
+    reactor.subscribe("test", |_, _| true, |_, _| (), &[id!(label_input), id!(no_such_widget)]);
+    reactor.dispatch(&mut (), harness.cx(), &ui, &Actions::default());
+    assert_eq!(reactor.redraws_this_frame(), 1);
+}
+
+#[test]
+fn counter_keyboard_shortcuts() {
+    let mut harness = TestHarness::new();
+    let none = KeyModifiers::default();
+    harness.press_key(KeyCode::Equals, KeyModifiers {shift: true, ..Default::default()});
+    harness.press_key(KeyCode::ArrowUp, none);
+    assert_eq!(harness.text(id!(label1)), "Label: 2");
+
+    harness.press_key(KeyCode::PageUp, none);
+    assert_eq!(harness.text(id!(label1)), "Label: 12");
+    harness.press_key(KeyCode::Minus, none);
+    assert_eq!(harness.text(id!(label1)), "Label: 11");
+
+    // Keys typed into a text input don't reach the counter.
+    harness.click(id!(input_sample.input));
+    harness.press_key(KeyCode::Home, none);
+    assert_eq!(harness.text(id!(label1)), "Label: 11");
+    harness.blur();
+
+    harness.press_key(KeyCode::Home, none);
+    assert_eq!(harness.text(id!(label1)), "Label: 0");
+
+    // Keyboard changes are undoable like clicks.
+    harness.press_key(KeyCode::KeyZ, KeyModifiers {control: true, ..Default::default()});
+    assert_eq!(harness.text(id!(label1)), "Label: 11");
+}
+
+#[test]
+fn combo_box_filter_keys_do_not_reach_counter() {
+    let mut harness = TestHarness::new();
+    let none = KeyModifiers::default();
+    harness.click(id!(instrument_combo));
+    harness.press_key(KeyCode::ArrowUp, none);
+    harness.press_key(KeyCode::Minus, none);
+    harness.press_key(KeyCode::Escape, none);
+    assert_eq!(harness.text(id!(label1)), "Label: 0");
+
+    // Closing the popup hands the keys back to the counter.
+    harness.press_key(KeyCode::ArrowUp, none);
+    assert_eq!(harness.text(id!(label1)), "Label: 1");
+}
+
+#[test]
+fn undo_redo_counter_and_input() {
+    let mut harness = TestHarness::new();
+    harness.click(id!(button1));
+    harness.click(id!(button1));
+    harness.type_text(id!(input_sample.input), "h");
+    harness.type_text(id!(input_sample.input), "hi");
+    harness.blur();
+
+    // Both text edits are undone at once.
+    harness.press_key(KeyCode::KeyZ, KeyModifiers {control: true, ..Default::default()});
+    assert_eq!(harness.text(id!(input_sample)), "");
+    assert_eq!(harness.text(id!(label_input)), "Input: ");
+
+    harness.click(id!(undo_button));
+    assert_eq!(harness.text(id!(label1)), "Label: 1");
+
+    harness.press_key(KeyCode::KeyZ, KeyModifiers {control: true, shift: true, ..Default::default()});
+    assert_eq!(harness.text(id!(label1)), "Label: 2");
+    harness.click(id!(redo_button));
+    assert_eq!(harness.text(id!(label_input)), "Input: hi");
+    assert!(!harness.app().history().can_redo());
+}
+
+#[test]
+fn undo_shortcut_is_left_to_focused_text_input() {
+    let mut harness = TestHarness::new();
+    harness.click(id!(button1));
+    harness.click(id!(button1));
+
+    // The text input handles the shortcut itself, the counter is left alone.
+    harness.click(id!(input_sample.input));
+    harness.press_key(KeyCode::KeyZ, KeyModifiers {control: true, ..Default::default()});
+    assert_eq!(harness.text(id!(label1)), "Label: 2");
+    assert!(harness.app().history().can_undo());
+
+    harness.blur();
+    harness.press_key(KeyCode::KeyZ, KeyModifiers {control: true, ..Default::default()});
+    assert_eq!(harness.text(id!(label1)), "Label: 1");
+}
+
+#[test]
+fn undo_dropdown_selection() {
+    let mut harness = TestHarness::new();
+    harness.type_text(id!(input_sample.input), "abc");
+    harness.blur();
+    harness.select(id!(my_dropdown.dropdown), 1);
+    assert_eq!(harness.app().transform(), live_id!(uppercase));
+    assert_eq!(harness.text(id!(label_input)), "Input: ABC");
+
+    harness.click(id!(undo_button));
+    assert_eq!(harness.app().ui().drop_down(id!(my_dropdown.dropdown)).selected_item(), 0);
+    assert_eq!(harness.app().transform(), live_id!(identity));
+    assert_eq!(harness.text(id!(label_input)), "Input: abc");
+}
+
+#[test]
+fn input_as_number_field_for_counter() {
+    let mut harness = TestHarness::new();
+    harness.click(id!(number_field));
+    assert_eq!(harness.text(id!(input_sample)), "0");
+
+    harness.type_text(id!(input_sample.input), "42");
+    assert_eq!(harness.text(id!(label1)), "Label: 42");
+
+    harness.click(id!(button1));
+    assert_eq!(harness.text(id!(input_sample)), "43");
+    assert_eq!(harness.text(id!(label_input)), "Input: 43");
+
+    // Invalid numbers are reported inline and leave the counter alone.
+    harness.type_text(id!(input_sample.input), "4x");
+    assert_eq!(harness.text(id!(input_sample.error)), "Must be a number");
+    harness.type_text(id!(input_sample.input), "-5");
+    assert_eq!(harness.text(id!(input_sample.error)), "Must be at least 0");
+    assert_eq!(harness.text(id!(label1)), "Label: 43");
+
+    // Both sides of an edit are undone together.
+    harness.type_text(id!(input_sample.input), "7");
+    assert_eq!(harness.text(id!(label1)), "Label: 7");
+    harness.click(id!(undo_button));
+    assert_eq!(harness.text(id!(label1)), "Label: 43");
+
+    harness.click(id!(number_field));
+    harness.type_text(id!(input_sample.input), "hello");
+    assert_eq!(harness.text(id!(input_sample.error)), "");
+    assert_eq!(harness.text(id!(label1)), "Label: 43");
+}
+
+#[test]
+fn input_is_validated() {
+    let mut harness = TestHarness::new();
+    let input = harness.app().ui().validated_input(id!(input_sample));
+    assert!(input.is_valid());
+
+    harness.type_text(id!(input_sample.input), &"x".repeat(41));
+    assert!(!input.is_valid());
+    assert_eq!(harness.text(id!(input_sample.error)), "At most 40 characters");
+
+    input.add_validator(Validator::custom(|text| {
+        if text.starts_with(' ') {Err("No leading spaces".to_string())} else {Ok(())}
+    }));
+    harness.type_text(id!(input_sample.input), " hello");
+    assert_eq!(input.error().as_deref(), Some("No leading spaces"));
+
+    harness.type_text(id!(input_sample.input), "hello");
+    assert!(input.is_valid());
+    assert_eq!(input.error(), None);
+    assert_eq!(harness.text(id!(input_sample.error)), "");
+}
+
+#[test]
+fn validators_follow_live_properties() {
+    let mut harness = TestHarness::new();
+    let input = harness.app().ui().validated_input(id!(input_sample));
+    harness.type_text(id!(input_sample.input), "hello world");
+    assert!(input.is_valid());
+
+    harness.widget(id!(input_sample)).apply_over(harness.cx(), live!{max_length: 5});
+    assert!(!input.is_valid());
+    harness.type_text(id!(input_sample.input), "hello!");
+    assert_eq!(harness.text(id!(input_sample.error)), "At most 5 characters");
+}
+
+#[test]
+fn number_field_keeps_dsl_numeric_flag() {
+    let mut harness = TestHarness::new();
+    harness.widget(id!(input_sample)).apply_over(harness.cx(), live!{numeric: true, min: 0.0, max: 50.0});
+
+    // Turning the number field on and off again leaves the declared range in place.
+    harness.click(id!(number_field));
+    harness.click(id!(number_field));
+    harness.type_text(id!(input_sample.input), "60");
+    assert_eq!(harness.text(id!(input_sample.error)), "Must be between 0 and 50");
+}
+
+#[test]
+fn numeric_input_with_one_bound() {
+    let mut harness = TestHarness::new();
+    harness.widget(id!(input_sample)).apply_over(harness.cx(), live!{numeric: true, min: 0.0});
+    harness.type_text(id!(input_sample.input), "-1");
+    assert_eq!(harness.text(id!(input_sample.error)), "Must be at least 0");
+    harness.type_text(id!(input_sample.input), "NaN");
+    assert_eq!(harness.text(id!(input_sample.error)), "Must be a number");
+}
+
+#[test]
+fn password_input_keeps_real_text() {
+    let mut harness = TestHarness::new();
+    let password = harness.app().ui().password_input(id!(password_input));
+    harness.type_text(id!(password_input.input), "hunter2");
+    assert_eq!(harness.text(id!(password_input)), "hunter2");
+    assert!(!password.is_revealed());
+
+    harness.click(id!(password_input.reveal));
+    assert!(password.is_revealed());
+    assert_eq!(harness.text(id!(password_input)), "hunter2");
+    harness.click(id!(password_input.reveal));
+    assert!(!password.is_revealed());
+
+    // The password isn't mirrored anywhere.
+    assert_eq!(harness.text(id!(label_input)), "Input: ");
+}
+
+#[test]
+fn notes_show_stats() {
+    let mut harness = TestHarness::new();
+    // Next to the single-line input.
+    assert!(!harness.widget(id!(InputView.notes_view.notes)).is_empty());
+    assert_eq!(harness.text(id!(notes_stats)), "0 lines, 0 words, 0 characters");
+
+    harness.type_text(id!(notes.input), "first line\nsecond");
+    assert_eq!(harness.text(id!(notes)), "first line\nsecond");
+    assert_eq!(harness.text(id!(notes_stats)), "2 lines, 3 words, 17 characters");
+}
+
+#[test]
+fn dropdown_selection() {
+    let mut harness = TestHarness::new();
+    harness.select(id!(selection_dropdown.dropdown), 2);
+    assert_eq!(harness.app().ui().drop_down(id!(selection_dropdown.dropdown)).selected_label(), "Selection 3");
+    assert_eq!(harness.app().selection(), Some(Selection::Selection3));
+    assert_eq!(
+        harness.app().store().nodes.read_field_value(id!(selection)),
+        Some(&LiveValue::Id(live_id!(sel3)))
+    );
+}
+
+#[test]
+fn dropdown_selection_is_typed() {
+    let mut harness = TestHarness::new();
+    assert_eq!(harness.app().selection(), None);
+
+    harness.select(id!(selection_dropdown.dropdown), 3);
+    assert_eq!(harness.app().selection(), Some(Selection::Selection4));
+
+    harness.with_app(|cx, app| app.set_selection(cx, Selection::Selection2));
+    harness.click(id!(button1));
+    assert_eq!(harness.app().selection(), Some(Selection::Selection2));
+    assert_eq!(harness.app().ui().drop_down(id!(selection_dropdown.dropdown)).selected_item(), 1);
+}
+
+#[test]
+fn dropdown_items_keep_selection_by_value() {
+    let mut harness = TestHarness::new();
+    let dropdown = harness.app().ui().drop_down(id!(items_dropdown.dropdown));
+    assert_eq!(dropdown.selected_label(), "Item 1");
+
+    assert_eq!(dropdown.items().len(), 3);
+    assert_eq!(dropdown.selected_value(), Some(LiveId::from_str("Item 1")));
+
+    harness.select(id!(items_dropdown.dropdown), 1);
+    harness.click(id!(add_item_button));
+    assert_eq!(dropdown.selected_item(), 2);
+    assert_eq!(dropdown.selected_label(), "Item 2");
+    assert_eq!(dropdown.items()[0], DropDownItem::from_label("Item 4"));
+
+    // The items are kept per dropdown, ones from the DSL aren't known.
+    let selection = harness.app().ui().drop_down(id!(selection_dropdown.dropdown));
+    assert!(selection.items().is_empty());
+    assert_eq!(selection.selected_value(), None);
+}
+
+#[test]
+fn multi_select_selection_is_bound() {
+    let mut harness = TestHarness::new();
+    let multi_select = harness.app().ui().multi_select_drop_down(id!(my_multi_select.dropdown));
+    assert!(multi_select.selected().is_empty());
+
+    let selected: HashSet<LiveId> = [live_id!(sel3), live_id!(sel1), live_id!(unknown)].into_iter().collect();
+    harness.with_app(|cx, _app| multi_select.set_selected(cx, &selected));
+    let expected: HashSet<LiveId> = [live_id!(sel1), live_id!(sel3)].into_iter().collect();
+    assert_eq!(multi_select.selected(), expected);
+
+    // The store holds the ids in the order of the items, and the next binding round keeps them.
+    assert_eq!(
+        read_ids(&harness.app().store().nodes, id!(my_multi_select)),
+        Some(vec![live_id!(sel1), live_id!(sel3)])
+    );
+    harness.click(id!(button1));
+    assert_eq!(multi_select.selected(), expected);
+}
+
+#[test]
+fn bound_values_are_saved_and_restored() {
+    let path = std::env::temp_dir().join(format!("makepad_widgets_sample_{}.ron", std::process::id()));
+
+    let mut harness = TestHarness::new();
+    harness.select(id!(my_dropdown.dropdown), 1);
+    harness.click(id!(button1));
+    harness.type_text(id!(input_sample.input), "saved");
+    let multi_select = harness.app().ui().multi_select_drop_down(id!(my_multi_select.dropdown));
+    let selected: HashSet<LiveId> = [live_id!(sel2), live_id!(sel4)].into_iter().collect();
+    harness.with_app(|cx, _app| multi_select.set_selected(cx, &selected));
+    harness.app().set_state_file(Some(path.clone()));
+    harness.send_event(&Event::Shutdown);
+
+    let mut restored = TestHarness::new();
+    restored.app().set_state_file(Some(path.clone()));
+    restored.send_event(&Event::Startup);
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(restored.text(id!(label1)), "Label: 1");
+    assert_eq!(restored.text(id!(input_sample)), "saved");
+    assert_eq!(restored.text(id!(label_input)), "Input: SAVED");
+    assert_eq!(restored.app().ui().drop_down(id!(my_dropdown.dropdown)).selected_item(), 1);
+    assert_eq!(restored.app().ui().multi_select_drop_down(id!(my_multi_select.dropdown)).selected(), selected);
+}
+
+#[test]
+fn shell_slides_page_shows_the_demo_deck() {
+    let mut harness = TestHarness::with_mode(AppMode::Shell);
+    let ui = harness.app().ui().clone();
+    assert!(!ui.widget(id!(slides_page.counter)).is_empty());
+    assert!(!ui.widget(id!(slides_page.buttonc1)).is_empty());
+
+    // The controls reached by id are the ones on the page shown.
+    harness.click(id!(button1));
+    assert_eq!(harness.text(id!(widgets_page.label1)), "Label: 1");
+    assert_eq!(harness.app().demo_ui().widget_uid(), ui.widget(id!(widgets_page)).widget_uid());
+    harness.with_app(|cx, app| app.set_page(cx, ShellPage::Slides));
+    assert_eq!(harness.app().demo_ui().widget_uid(), ui.widget(id!(slides_page)).widget_uid());
+    // The deck shows the state of the app, not that of its own copy of the widgets.
+    assert_eq!(harness.text(id!(slides_page.label1)), "Label: 1");
+}
+
+#[test]
+fn clicking_a_tab_shows_its_page() {
+    let mut harness = TestHarness::with_mode(AppMode::Shell);
+    let is_drawn = |harness: &mut TestHarness, path: &[LiveId]| {
+        harness.draw();
+        let area = harness.widget(path).area();
+        area.is_valid(harness.cx())
+    };
+    assert!(is_drawn(&mut harness, id!(widgets_page)));
+    assert!(!is_drawn(&mut harness, id!(layout_page)));
+
+    harness.click(id!(layout_tab));
+    assert_eq!(harness.app().page(), ShellPage::Layout);
+    assert!(is_drawn(&mut harness, id!(layout_page)));
+    assert!(!is_drawn(&mut harness, id!(widgets_page)));
+
+    harness.click(id!(slides_tab));
+    assert_eq!(harness.app().page(), ShellPage::Slides);
+    assert!(is_drawn(&mut harness, id!(slides_page)));
+    assert!(!is_drawn(&mut harness, id!(layout_page)));
+}
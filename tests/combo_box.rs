@@ -0,0 +1,56 @@
+use makepad_widgets::*;
+use makepad_widgets_sample::combo_box::*;
+use makepad_widgets_sample::test_harness::TestHarness;
+
+#[test]
+fn empty_filter_matches_everything() {
+    assert_eq!(match_score("Piano", ""), Some(0));
+}
+
+#[test]
+fn substring_matches_rank_by_position() {
+    assert_eq!(match_score("Violin", "vio"), Some(0));
+    assert_eq!(match_score("Viola", "OLA"), Some(2));
+    assert!(match_score("Cello", "llo") < match_score("Cello", "clo"));
+}
+
+#[test]
+fn fuzzy_matches_rank_after_substring_matches() {
+    let fuzzy = match_score("Synthesizer", "syz").unwrap();
+    assert!(fuzzy >= "synthesizer".len());
+    assert!(match_score("Saxophone", "sx") < match_score("Synthesizer", "sz"));
+}
+
+#[test]
+fn characters_out_of_order_do_not_match() {
+    assert_eq!(match_score("Tuba", "abt"), None);
+    assert_eq!(match_score("Oboe", "x"), None);
+}
+
+#[test]
+fn highlight_reaches_every_item() {
+    let mut harness = TestHarness::new();
+    let combo = harness.app().ui().combo_box(id!(instrument_combo));
+    let none = KeyModifiers::default();
+
+    // More items than fit in the popup, reached by scrolling down with the highlight.
+    harness.click(id!(instrument_combo));
+    for _ in 0..10 {
+        harness.press_key(KeyCode::ArrowDown, none);
+    }
+    harness.press_key(KeyCode::ReturnKey, none);
+    assert_eq!(combo.selected_item(), 10);
+
+    // The highlight wraps around over all of the items.
+    harness.click(id!(instrument_combo));
+    for _ in 0..14 {
+        harness.press_key(KeyCode::ArrowDown, none);
+    }
+    harness.press_key(KeyCode::ReturnKey, none);
+    assert_eq!(combo.selected_item(), 0);
+
+    harness.click(id!(instrument_combo));
+    harness.press_key(KeyCode::ArrowUp, none);
+    harness.press_key(KeyCode::ReturnKey, none);
+    assert_eq!(combo.selected_item(), 23);
+}
@@ -0,0 +1,29 @@
+use makepad_widgets_sample::counter::OverflowPolicy;
+
+#[test]
+fn values_within_bounds_are_kept() {
+    for policy in [OverflowPolicy::Clamp, OverflowPolicy::Wrap, OverflowPolicy::Reject] {
+        assert_eq!(policy.apply(5.0, 0.0, 9.0, 1.0), Some(5.0));
+        assert_eq!(policy.apply(9.0, 0.0, 9.0, 1.0), Some(9.0));
+    }
+}
+
+#[test]
+fn clamp() {
+    assert_eq!(OverflowPolicy::Clamp.apply(10.0, 0.0, 9.0, 1.0), Some(9.0));
+    assert_eq!(OverflowPolicy::Clamp.apply(-1.0, 0.0, 9.0, 1.0), Some(0.0));
+}
+
+#[test]
+fn wrap() {
+    assert_eq!(OverflowPolicy::Wrap.apply(10.0, 0.0, 9.0, 1.0), Some(0.0));
+    assert_eq!(OverflowPolicy::Wrap.apply(-1.0, 0.0, 9.0, 1.0), Some(9.0));
+    assert_eq!(OverflowPolicy::Wrap.apply(1.5, -1.0, 1.0, 0.5), Some(-1.0));
+    // Without both bounds there is nothing to wrap to.
+    assert_eq!(OverflowPolicy::Wrap.apply(-1.0, 0.0, f64::MAX, 1.0), Some(0.0));
+}
+
+#[test]
+fn reject() {
+    assert_eq!(OverflowPolicy::Reject.apply(10.0, 0.0, 9.0, 1.0), None);
+}
@@ -0,0 +1,126 @@
+use makepad_widgets::*;
+use makepad_widgets_sample::debug_layout::*;
+use makepad_widgets_sample::launch::AppMode;
+use makepad_widgets_sample::test_harness::TestHarness;
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> Rect {
+    Rect {pos: dvec2(x, y), size: dvec2(w, h)}
+}
+
+fn padding(left: f64, top: f64, right: f64, bottom: f64) -> Padding {
+    Padding {left, top, right, bottom}
+}
+
+fn sides(padding: Padding) -> (f64, f64, f64, f64) {
+    (padding.left, padding.top, padding.right, padding.bottom)
+}
+
+#[test]
+fn padding_and_margin_bands() {
+    let r = rect(10.0, 20.0, 100.0, 50.0);
+    assert_eq!(outset(r, &padding(1.0, 2.0, 3.0, 4.0)), rect(9.0, 18.0, 104.0, 56.0));
+    assert_eq!(inset(r, &padding(1.0, 2.0, 3.0, 4.0)), rect(11.0, 22.0, 96.0, 44.0));
+    assert_eq!(inset(r, &padding(60.0, 0.0, 60.0, 0.0)).size.x, 0.0);
+
+    let [top, bottom, left, right] = band(r, inset(r, &padding(1.0, 2.0, 3.0, 4.0)));
+    assert_eq!(top, rect(10.0, 20.0, 100.0, 2.0));
+    assert_eq!(bottom, rect(10.0, 66.0, 100.0, 4.0));
+    assert_eq!(left, rect(10.0, 22.0, 1.0, 44.0));
+    assert_eq!(right, rect(107.0, 22.0, 3.0, 44.0));
+}
+
+#[test]
+fn drawn_widgets_follow_the_runtime_layout() {
+    let mut harness = TestHarness::new();
+    harness.draw();
+    let widgets = harness.with_app(|cx, app| {
+        let tree = app.layout_tree(cx);
+        drawn_widgets(cx, &tree)
+    });
+    let find = |widgets: &[DrawnWidget], ids: &[LiveId]| widgets.iter().find(|widget| widget.path.ends_with(ids)).cloned();
+
+    let dropdown = find(&widgets, id!(my_dropdown)).unwrap();
+    assert_eq!(dropdown.id(), live_id!(my_dropdown));
+    assert!(dropdown.rect.is_some());
+    assert_eq!(dropdown.margin.top, 7.5);
+    assert_eq!(dropdown.margin.left, 0.0);
+    assert!(find(&widgets, id!(counter.button1)).is_some());
+    assert!(find(&widgets, id!(debug_overlay)).is_none());
+
+    // Margins changed from Rust are picked up.
+    harness.with_app(|cx, app| app.ui().widget(id!(my_dropdown)).apply_over(cx, live!{margin: {top: 3.0}}));
+    harness.draw();
+    let widgets = harness.with_app(|cx, app| {
+        let tree = app.layout_tree(cx);
+        drawn_widgets(cx, &tree)
+    });
+    assert_eq!(find(&widgets, id!(my_dropdown)).unwrap().margin.top, 3.0);
+}
+
+#[test]
+fn drawn_widgets_include_alignment_cells() {
+    let mut harness = TestHarness::with_mode(AppMode::Layout);
+    harness.click(id!(alignment_tab));
+    harness.draw();
+    let widgets = harness.with_app(|cx, app| {
+        let tree = app.layout_tree(cx);
+        drawn_widgets(cx, &tree)
+    });
+    let cells: Vec<_> = widgets
+        .iter()
+        .filter(|widget| widget.path.len() >= 2 && widget.path[widget.path.len() - 2] == live_id!(down_matrix))
+        .collect();
+    assert_eq!(cells.len(), 9);
+    assert_eq!(cells[0].id().to_string(), "cell0");
+
+    // The padding of the cells comes from their template. The space the alignment leaves free in
+    // the last cell isn't counted.
+    assert_eq!(sides(cells[0].padding), (4.0, 4.0, 4.0, 4.0));
+    assert_eq!(sides(cells[8].padding), (4.0, 4.0, 4.0, 4.0));
+    let label = widgets.iter().find(|widget| widget.path.ends_with(&[cells[0].id(), live_id!(label)]));
+    assert!(label.is_some_and(|label| label.rect.is_some()));
+
+    // Hidden sections are listed without their children.
+    let rows = widgets.iter().find(|widget| widget.id() == live_id!(rows_section)).unwrap();
+    assert!(rows.rect.is_none());
+    assert!(!widgets.iter().any(|widget| widget.id() == live_id!(buttonc22)));
+}
+
+#[test]
+fn responsive_views_report_their_applied_padding() {
+    let mut harness = TestHarness::new();
+    harness.resize(390.0, 844.0);
+    harness.widget(id!(InputView)).apply_over(harness.cx(), live!{narrow: {padding: {left: 6.0}}});
+    // The layout is only reapplied when the side of the breakpoint changes.
+    harness.resize(1280.0, 1080.0);
+    harness.resize(390.0, 844.0);
+    let widgets = harness.with_app(|cx, app| {
+        let tree = app.layout_tree(cx);
+        drawn_widgets(cx, &tree)
+    });
+    let input_view = widgets.iter().find(|widget| widget.id() == live_id!(InputView)).unwrap();
+    assert_eq!(sides(input_view.padding), (6.0, 0.0, 0.0, 0.0));
+}
+
+#[test]
+fn union_of_rects() {
+    assert_eq!(union(rect(0.0, 0.0, 10.0, 10.0), rect(5.0, -5.0, 10.0, 10.0)), rect(0.0, -5.0, 15.0, 15.0));
+}
+
+#[test]
+fn f12_toggles_the_overlay() {
+    let mut harness = TestHarness::new();
+    assert!(!harness.widget(id!(debug_overlay)).as_layout_overlay().is_enabled());
+
+    harness.press_key(KeyCode::F12, KeyModifiers::default());
+    assert!(harness.app().debug_layout());
+    assert!(harness.widget(id!(debug_overlay)).as_layout_overlay().is_enabled());
+
+    // A new window gets the overlay too.
+    harness.with_app(|cx, app| app.set_mode(cx, AppMode::Shell));
+    assert!(harness.widget(id!(debug_overlay)).as_layout_overlay().is_enabled());
+
+    harness.press_key(KeyCode::F12, KeyModifiers::default());
+    assert!(!harness.app().debug_layout());
+    assert!(!harness.widget(id!(debug_overlay)).as_layout_overlay().is_enabled());
+}
@@ -0,0 +1,83 @@
+use makepad_widgets::*;
+use makepad_widgets_sample::history::*;
+
+fn int(field: &[LiveId], old: i64, new: i64) -> Change {
+    Change::new(field, LiveValue::Int64(old), LiveValue::Int64(new))
+}
+
+fn text(field: &[LiveId], old: &str, new: &str) -> Change {
+    Change::new(field, LiveValue::String(old.to_string().into()), LiveValue::String(new.to_string().into()))
+}
+
+#[test]
+fn undo_and_redo() {
+    let mut history = History::default();
+    history.push(vec![int(id!(counter), 0, 1)]);
+    history.push(vec![int(id!(counter), 1, 2)]);
+
+    assert_eq!(history.undo(), Some(vec![int(id!(counter), 1, 2)]));
+    assert_eq!(history.undo(), Some(vec![int(id!(counter), 0, 1)]));
+    assert_eq!(history.undo(), None);
+    assert_eq!(history.redo(), Some(vec![int(id!(counter), 0, 1)]));
+    assert!(history.can_undo());
+    assert!(history.can_redo());
+}
+
+#[test]
+fn new_edit_clears_redo() {
+    let mut history = History::default();
+    history.push(vec![int(id!(counter), 0, 1)]);
+    history.undo();
+    history.push(vec![int(id!(counter), 0, 5)]);
+    assert!(!history.can_redo());
+}
+
+#[test]
+fn text_edits_are_coalesced() {
+    let mut history = History::default();
+    history.push(vec![text(id!(input), "", "h")]);
+    history.push(vec![text(id!(input), "h", "he")]);
+    history.push(vec![text(id!(input), "he", "hey")]);
+    assert_eq!(history.undo(), Some(vec![text(id!(input), "", "hey")]));
+    assert!(!history.can_undo());
+
+    // Typing after an undo starts a new entry.
+    history.redo();
+    history.push(vec![text(id!(input), "hey", "hey!")]);
+    assert_eq!(history.undo(), Some(vec![text(id!(input), "hey", "hey!")]));
+    assert_eq!(history.undo(), Some(vec![text(id!(input), "", "hey")]));
+}
+
+#[test]
+fn unchanged_values_are_not_recorded() {
+    let mut history = History::default();
+    history.push(vec![int(id!(counter), 3, 3)]);
+    assert!(!history.can_undo());
+}
+
+#[test]
+fn oldest_entries_are_dropped() {
+    let mut history = History::new(2);
+    for i in 0..3 {
+        history.push(vec![int(id!(counter), i, i + 1)]);
+    }
+    assert!(history.undo().is_some());
+    assert!(history.undo().is_some());
+    assert!(history.undo().is_none());
+}
+
+#[test]
+fn grouped_edits_are_merged() {
+    let mut history = History::default();
+    history.push(vec![int(id!(counter), 0, 1)]);
+    history.begin_group();
+    history.push(vec![int(id!(counter), 1, 2)]);
+    history.push(vec![int(id!(counter), 2, 3)]);
+    history.push(vec![int(id!(counter), 3, 4)]);
+    history.end_group();
+    history.push(vec![int(id!(counter), 4, 5)]);
+
+    assert_eq!(history.undo(), Some(vec![int(id!(counter), 4, 5)]));
+    assert_eq!(history.undo(), Some(vec![int(id!(counter), 1, 4)]));
+    assert_eq!(history.undo(), Some(vec![int(id!(counter), 0, 1)]));
+}
@@ -0,0 +1,69 @@
+use makepad_widgets_sample::launch::*;
+use std::path::PathBuf;
+
+fn parse(args: &[&str], env: &[(&str, &str)]) -> LaunchOptions {
+    let env: Vec<(String, String)> = env.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+    LaunchOptions::parse(
+        args.iter().map(|arg| arg.to_string()),
+        |name| env.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone()),
+    )
+}
+
+#[test]
+fn state_file_options() {
+    assert_eq!(parse(&[], &[]).state_file, StateFile::Default);
+    assert_eq!(
+        parse(&["--state-file", "/tmp/a.ron"], &[]).state_file,
+        StateFile::Path(PathBuf::from("/tmp/a.ron"))
+    );
+    assert_eq!(
+        parse(&[], &[(STATE_FILE_ENV_VAR, "/tmp/b.ron")]).state_file,
+        StateFile::Path(PathBuf::from("/tmp/b.ron"))
+    );
+    assert_eq!(parse(&["--no-state"], &[(STATE_FILE_ENV_VAR, "/tmp/b.ron")]).state_file, StateFile::Disabled);
+    assert_eq!(parse(&["--state-file="], &[]).state_file, StateFile::Disabled);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn data_dir_on_linux() {
+    let env = |vars: &'static [(&'static str, &'static str)]| {
+        move |name: &str| vars.iter().find(|(k, _)| *k == name).map(|(_, v)| v.to_string())
+    };
+    assert_eq!(
+        data_dir_from_env(env(&[("HOME", "/home/me")])),
+        Some(PathBuf::from("/home/me/.local/share"))
+    );
+    assert_eq!(
+        data_dir_from_env(env(&[("HOME", "/home/me"), ("XDG_DATA_HOME", "/data")])),
+        Some(PathBuf::from("/data"))
+    );
+    assert_eq!(data_dir_from_env(env(&[("HOME", "")])), None);
+}
+
+#[test]
+fn mode_options() {
+    assert_eq!(parse(&[], &[]).mode, AppMode::Shell);
+    assert_eq!(parse(&["--mode", "layout"], &[]).mode, AppMode::Layout);
+    assert_eq!(parse(&["--mode=Slides"], &[]).mode, AppMode::Slides);
+    assert_eq!(parse(&[], &[(MODE_ENV_VAR, "widgets")]).mode, AppMode::Widgets);
+}
+
+#[test]
+fn unknown_modes_are_ignored() {
+    assert_eq!(parse(&["--mode", "nope"], &[]).mode, AppMode::Shell);
+    assert_eq!(parse(&[], &[(MODE_ENV_VAR, "nope")]).mode, AppMode::Shell);
+    // An unknown mode on the command line leaves the one from the environment.
+    assert_eq!(parse(&["--mode", "nope"], &[(MODE_ENV_VAR, "layout")]).mode, AppMode::Layout);
+    // `--mode` without a value is ignored too.
+    assert_eq!(parse(&["--mode"], &[]).mode, AppMode::Shell);
+}
+
+#[test]
+fn command_line_wins_over_environment() {
+    assert_eq!(parse(&["--mode", "slides"], &[(MODE_ENV_VAR, "layout")]).mode, AppMode::Slides);
+    assert_eq!(
+        parse(&["--state-file", "/tmp/a.ron"], &[(STATE_FILE_ENV_VAR, "/tmp/b.ron")]).state_file,
+        StateFile::Path(PathBuf::from("/tmp/a.ron"))
+    );
+}
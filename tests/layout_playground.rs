@@ -0,0 +1,38 @@
+use makepad_widgets::*;
+use makepad_widgets_sample::app::LayoutSection;
+use makepad_widgets_sample::launch::AppMode;
+use makepad_widgets_sample::layout_playground::*;
+use makepad_widgets_sample::test_harness::TestHarness;
+
+#[test]
+fn snippet() {
+    let layout = PlaygroundLayout {
+        flow: Flow::Down,
+        align_x: 0.5,
+        align_y: 1.0,
+        spacing: 10.0,
+        padding: 4.0,
+        margin: 0.0,
+    };
+    assert_eq!(
+        layout.snippet(),
+        "<View> {\n    flow: Down,\n    align: {x: 0.5, y: 1},\n    spacing: 10,\n    padding: 4,\n    margin: 0,\n}"
+    );
+}
+
+#[test]
+fn controls_update_snippet() {
+    // The playground is a section of `LayoutView`, reached in the layout mode too.
+    let mut harness = TestHarness::with_mode(AppMode::Layout);
+    harness.click(id!(playground_tab));
+    assert_eq!(harness.app().layout_section(), LayoutSection::Playground);
+    harness.send_widget_action(id!(playground.align_x), SliderAction::Slide(0.5));
+    harness.send_widget_action(id!(playground.spacing), SliderAction::Slide(12.0));
+    harness.select(id!(playground.flow_dropdown), 1);
+
+    let layout = harness.app().ui().layout_playground(id!(playground)).layout();
+    assert_eq!(layout.align_x, 0.5);
+    assert_eq!(layout.spacing, 12.0);
+    assert!(matches!(layout.flow, Flow::Down));
+    assert_eq!(harness.text(id!(playground.snippet)), layout.snippet());
+}
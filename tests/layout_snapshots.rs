@@ -0,0 +1,106 @@
+use makepad_widgets_sample::launch::AppMode;
+use makepad_widgets_sample::test_harness::TestHarness;
+use std::path::PathBuf;
+
+// Golden-file tests for the layout of the widgets and layout demos. Each snapshot is compared with
+// `tests/snapshots/<name>.json`. After an intended layout change, rerun with `UPDATE_SNAPSHOTS=1`
+// to rewrite the golden files, and review the diff before committing them:
+//
+//     UPDATE_SNAPSHOTS=1 cargo test --test layout_snapshots
+
+const SIZES: [(&str, f64, f64); 3] = [
+    ("phone", 390.0, 844.0),
+    ("tablet", 768.0, 1024.0),
+    ("desktop", 1280.0, 1080.0),
+];
+
+fn assert_snapshot(name: &str, json: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots").join(format!("{}.json", name));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, json).unwrap();
+        return;
+    }
+    let Ok(expected) = std::fs::read_to_string(&path) else {
+        panic!("No snapshot at {}, run with UPDATE_SNAPSHOTS=1 to create it", path.display());
+    };
+    if expected != json {
+        panic!(
+            "Layout of {} changed, run with UPDATE_SNAPSHOTS=1 if intended:\n{}",
+            name,
+            line_diff(&expected, json).join("\n")
+        );
+    }
+}
+
+// The lines of `actual` that differ from `expected`, from a longest common subsequence of the two:
+// removed lines start with `-`, added ones with `+`, and both with their line number in their file.
+fn line_diff(expected: &str, actual: &str) -> Vec<String> {
+    let expected: Vec<_> = expected.lines().collect();
+    let actual: Vec<_> = actual.lines().collect();
+    // `common[i][j]` is the length of the longest common subsequence of `expected[i..]` and
+    // `actual[j..]`.
+    let mut common = vec![vec![0; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            i += 1;
+            j += 1;
+        } else if j == actual.len() || (i < expected.len() && common[i + 1][j] >= common[i][j + 1]) {
+            diff.push(format!("-{:>5} {}", i + 1, expected[i]));
+            i += 1;
+        } else {
+            diff.push(format!("+{:>5} {}", j + 1, actual[j]));
+            j += 1;
+        }
+    }
+    diff
+}
+
+#[test]
+fn line_diff_keeps_lines_in_common() {
+    let diff = line_diff("a\nb\nc\nd\n", "a\nc\nx\nd\n");
+    assert_eq!(diff, ["-    2 b", "+    3 x"]);
+    assert_eq!(line_diff("a\n", "a\nb\n"), ["+    2 b"]);
+    assert!(line_diff("a\nb\n", "a\nb\n").is_empty());
+}
+
+fn check_mode(mode: AppMode, prefix: &str) {
+    for (size, width, height) in SIZES {
+        let mut harness = TestHarness::with_mode(mode);
+        let snapshot = harness.layout_snapshot(width, height);
+        assert!(!snapshot.widgets.is_empty());
+        assert_snapshot(&format!("{}_{}", prefix, size), &snapshot.to_json());
+    }
+}
+
+#[test]
+fn widgets_layout() {
+    check_mode(AppMode::Widgets, "widgets");
+}
+
+#[test]
+fn layout_layout() {
+    check_mode(AppMode::Layout, "layout");
+}
+
+#[test]
+fn body_fills_the_window() {
+    let mut harness = TestHarness::with_mode(AppMode::Layout);
+    let snapshot = harness.layout_snapshot(768.0, 1024.0);
+    let json = snapshot.to_json();
+    assert!(json.starts_with("{\n  \"size\": [768, 1024],\n"));
+    let body = snapshot.widgets.iter().find(|widget| widget.path.len() == 1).unwrap();
+    let rect = body.rect.unwrap();
+    assert_eq!((rect.size.x, rect.size.y), (768.0, 1024.0));
+}
@@ -0,0 +1,27 @@
+use makepad_widgets::*;
+use makepad_widgets_sample::persist::*;
+
+#[test]
+fn store_round_trips_with_id_names() {
+    let path = std::env::temp_dir().join(format!("makepad_sample_persist_{}.ron", std::process::id()));
+    let named = LiveId::from_str_with_lut("persist_fruit").unwrap();
+    let unnamed = LiveId::from_str("Item 9");
+
+    let mut store = DataBindingStore::new();
+    store.nodes.write_field_value(&[named], LiveValue::Id(named));
+    store.nodes.write_field_value(&[LiveId::from_str_with_lut("persist_count").unwrap()], LiveValue::Float64(2.5));
+    write_ids(&mut store.nodes, &[LiveId::from_str_with_lut("persist_ids").unwrap()], &[named, unnamed]);
+    save_store(&store, &path).unwrap();
+
+    let saved = std::fs::read_to_string(&path).unwrap();
+    assert!(saved.contains("\"persist_fruit\""));
+    assert!(saved.contains(&format!("\"#{:016x}\"", unnamed.0)));
+    assert!(!path.with_file_name(format!("{}.tmp", path.file_name().unwrap().to_string_lossy())).exists());
+
+    let mut restored = DataBindingStore::new();
+    load_store(&mut restored, &path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(restored.nodes.read_field_value(&[named]), Some(&LiveValue::Id(named)));
+    assert_eq!(restored.nodes.read_field_value(id!(persist_count)), Some(&LiveValue::Float64(2.5)));
+    assert_eq!(read_ids(&restored.nodes, id!(persist_ids)), Some(vec![named, unnamed]));
+}
@@ -0,0 +1,49 @@
+use makepad_widgets::*;
+use makepad_widgets_sample::launch::AppMode;
+use makepad_widgets_sample::responsive::*;
+use makepad_widgets_sample::test_harness::TestHarness;
+
+#[test]
+fn views_follow_the_window_width() {
+    let mut harness = TestHarness::new();
+    let input_view = harness.widget(id!(InputView)).as_responsive_view();
+    let button_view = harness.widget(id!(ButtonView)).as_responsive_view();
+    assert!(!input_view.is_narrow());
+
+    harness.resize(390.0, 844.0);
+    assert!(input_view.is_narrow());
+    assert!(button_view.is_narrow());
+
+    harness.resize(1280.0, 1080.0);
+    assert!(!input_view.is_narrow());
+    assert!(!button_view.is_narrow());
+
+    // Exactly at the breakpoint the wide layout still applies.
+    harness.resize(800.0, 600.0);
+    assert!(!input_view.is_narrow());
+    harness.resize(799.0, 600.0);
+    assert!(input_view.is_narrow());
+}
+
+#[test]
+fn new_windows_get_the_current_layout() {
+    let mut harness = TestHarness::new();
+    harness.resize(390.0, 844.0);
+    harness.with_app(|cx, app| app.set_mode(cx, AppMode::Shell));
+    harness.draw();
+    assert!(harness.widget(id!(InputView)).as_responsive_view().is_narrow());
+}
+
+#[test]
+fn narrow_button_view_stacks_the_history_bar() {
+    let mut harness = TestHarness::new();
+    harness.resize(1280.0, 1080.0);
+    let counter = harness.center(id!(counter));
+    let history_bar = harness.center(id!(HistoryBar));
+    assert!(history_bar.x > counter.x);
+
+    harness.resize(390.0, 844.0);
+    let counter = harness.center(id!(counter));
+    let history_bar = harness.center(id!(HistoryBar));
+    assert!(history_bar.y > counter.y);
+}
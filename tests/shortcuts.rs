@@ -0,0 +1,15 @@
+use makepad_widgets::*;
+use makepad_widgets_sample::shortcuts::*;
+
+#[test]
+fn key_names() {
+    assert_eq!(key_code_from_name("Equals"), Some(KeyCode::Equals));
+    assert_eq!(key_code_from_name("+"), Some(KeyCode::Equals));
+    assert_eq!(key_code_from_name("Up"), Some(KeyCode::ArrowUp));
+    assert_eq!(key_code_from_name(" PageDown "), Some(KeyCode::PageDown));
+    assert_eq!(key_code_from_name("KeyJ"), Some(KeyCode::KeyJ));
+    assert_eq!(key_code_from_name("F5"), Some(KeyCode::F5));
+    assert_eq!(key_code_from_name("Numpad7"), Some(KeyCode::Numpad7));
+    assert_eq!(key_code_from_name("Hyper"), None);
+    assert_eq!(key_code_from_name("Shift"), None);
+}
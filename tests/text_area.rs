@@ -0,0 +1,9 @@
+use makepad_widgets_sample::text_area::*;
+
+#[test]
+fn stats() {
+    assert_eq!(text_stats(""), TextStats {lines: 0, words: 0, chars: 0});
+    assert_eq!(text_stats("one"), TextStats {lines: 1, words: 1, chars: 3});
+    assert_eq!(text_stats("one two\nthree\n"), TextStats {lines: 3, words: 3, chars: 14});
+    assert_eq!(text_stats("héllo"), TextStats {lines: 1, words: 1, chars: 5});
+}
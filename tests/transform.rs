@@ -0,0 +1,42 @@
+use makepad_widgets::*;
+use makepad_widgets_sample::transform::*;
+
+#[test]
+fn builtin_transforms() {
+    let registry = TransformRegistry::with_builtins();
+    let apply = |id, text| registry.apply(id, text);
+    assert_eq!(apply(live_id!(identity), "Hello"), "Hello");
+    assert_eq!(apply(live_id!(uppercase), "Hello"), "HELLO");
+    assert_eq!(apply(live_id!(lowercase), "Hello"), "hello");
+    assert_eq!(apply(live_id!(reverse), "Hello"), "olleH");
+    assert_eq!(apply(live_id!(slugify), "  Hello, World! 42 "), "hello-world-42");
+    assert_eq!(apply(live_id!(count), "two words"), "9 characters, 2 words");
+}
+
+#[test]
+fn base64() {
+    let cases = [("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"), ("foobar", "Zm9vYmFy"), ("é", "w6k=")];
+    for (text, encoded) in cases {
+        assert_eq!(Base64.apply(text), encoded);
+    }
+}
+
+struct Shout;
+
+impl TextTransform for Shout {
+    fn id(&self) -> LiveId {live_id!(shout)}
+    fn label(&self) -> &str {"Shout"}
+    fn apply(&self, text: &str) -> String {
+        format!("{}!", text.to_uppercase())
+    }
+}
+
+#[test]
+fn custom_transforms() {
+    let mut registry = TransformRegistry::with_builtins();
+    registry.register(Box::new(Shout));
+    assert_eq!(registry.apply(live_id!(shout), "hey"), "HEY!");
+    assert_eq!(registry.items().last().map(|item| item.label.as_str()), Some("Shout"));
+    // Unknown ids leave the text alone.
+    assert_eq!(registry.apply(live_id!(missing), "hey"), "hey");
+}
@@ -0,0 +1,65 @@
+use makepad_widgets::*;
+use makepad_widgets_sample::dropdown_items::*;
+use makepad_widgets_sample::launch::AppMode;
+use makepad_widgets_sample::typed_dropdown::*;
+use makepad_widgets_sample::test_harness::TestHarness;
+
+// Declared without any imports in scope.
+mod fruit {
+    makepad_widgets_sample::drop_down_enum!{
+        pub enum Fruit {
+            Apple = apple,
+            Pear = pear,
+        }
+    }
+}
+
+mod flow {
+    makepad_widgets_sample::drop_down_enum!{
+        pub enum FlowItem {
+            Right = Right,
+            Down = Down,
+            Overlay = Overlay,
+            RightWrap = RightWrap,
+        }
+    }
+}
+
+use fruit::Fruit;
+use flow::FlowItem;
+
+#[test]
+fn live_ids() {
+    assert_eq!(Fruit::Pear.live_id(), live_id!(pear));
+    assert_eq!(Fruit::from_live_id(live_id!(apple)), Some(Fruit::Apple));
+    assert_eq!(Fruit::from_live_id(live_id!(banana)), None);
+    assert_eq!(Fruit::ALL, &[Fruit::Apple, Fruit::Pear]);
+}
+
+#[test]
+fn selected_enum() {
+    let mut harness = TestHarness::with_mode(AppMode::Shell);
+    let dropdown = harness.app().ui().drop_down(id!(playground.flow_dropdown));
+    assert_eq!(dropdown.selected_enum::<FlowItem>(), Some(FlowItem::Right));
+
+    dropdown.set_selected_enum(harness.cx(), FlowItem::Overlay);
+    assert_eq!(dropdown.selected_item(), 2);
+    assert_eq!(dropdown.selected_enum::<FlowItem>(), Some(FlowItem::Overlay));
+}
+
+#[test]
+fn runtime_items_are_mapped_by_value() {
+    let mut harness = TestHarness::with_mode(AppMode::Shell);
+    let dropdown = harness.app().ui().drop_down(id!(playground.flow_dropdown));
+    let items = vec![DropDownItem::new(live_id!(pear), "Pear"), DropDownItem::new(live_id!(apple), "Apple")];
+    dropdown.set_items(harness.cx(), items);
+    assert_eq!(dropdown.selected_enum::<Fruit>(), Some(Fruit::Pear));
+    dropdown.set_selected_enum(harness.cx(), Fruit::Apple);
+    assert_eq!(dropdown.selected_item(), 1);
+    assert_eq!(dropdown.selected_enum::<Fruit>(), Some(Fruit::Apple));
+
+    // Items without a variant, e.g. after adding one the enum doesn't have.
+    let items = vec![DropDownItem::new(live_id!(banana), "Banana")];
+    dropdown.set_items(harness.cx(), items);
+    assert_eq!(dropdown.selected_enum::<Fruit>(), None);
+}
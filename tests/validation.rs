@@ -0,0 +1,49 @@
+use makepad_widgets_sample::validation::Validator;
+use regex::Regex;
+
+#[test]
+fn required() {
+    assert!(Validator::Required.validate("").is_err());
+    assert!(Validator::Required.validate("  ").is_err());
+    assert!(Validator::Required.validate("a").is_ok());
+}
+
+#[test]
+fn max_length_counts_characters() {
+    assert!(Validator::MaxLength(3).validate("äöü").is_ok());
+    assert!(Validator::MaxLength(3).validate("abcd").is_err());
+}
+
+#[test]
+fn range() {
+    let range = Validator::Range(0.0, 10.0);
+    assert!(range.validate("").is_ok());
+    assert!(range.validate("2.5").is_ok());
+    assert_eq!(range.validate("11"), Err("Must be between 0 and 10".to_string()));
+    assert_eq!(range.validate("ten"), Err("Must be a number".to_string()));
+    for text in ["NaN", "inf", "-inf", "infinity"] {
+        assert_eq!(range.validate(text), Err("Must be a number".to_string()), "{}", text);
+    }
+
+    // Unbounded sides, as for a counter without `max`.
+    assert_eq!(Validator::Range(0.0, f64::MAX).validate("-1"), Err("Must be at least 0".to_string()));
+    assert_eq!(Validator::Range(f64::MIN, 0.0).validate("1"), Err("Must be at most 0".to_string()));
+}
+
+#[test]
+fn rounded_range() {
+    // Rounded to 1.00, as a counter with `precision: 2` would store it.
+    assert!(Validator::RoundedRange(0.0, 1.0, 2).validate("1.004").is_ok());
+    assert!(Validator::Range(0.0, 1.0).validate("1.004").is_err());
+    assert_eq!(
+        Validator::RoundedRange(0.0, 1.0, 2).validate("1.006"),
+        Err("Must be between 0 and 1".to_string())
+    );
+}
+
+#[test]
+fn regex() {
+    let digits = Validator::Regex(Regex::new("^[0-9]+$").unwrap(), "Digits only".to_string());
+    assert!(digits.validate("123").is_ok());
+    assert_eq!(digits.validate("12a"), Err("Digits only".to_string()));
+}